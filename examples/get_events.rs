@@ -0,0 +1,24 @@
+use netatmo_rs::client::{get_events::GetEventsParameters, ids::HomeId, NetatmoClient};
+use std::env;
+
+#[tokio::main]
+async fn main() {
+    let access_token = env::var_os("NETATMO_ACCESS_TOKEN")
+        .expect("Environment variable 'NETATMO_ACCESS_TOKEN' is not set.")
+        .to_string_lossy()
+        .to_string();
+    let home_id = HomeId::new(
+        &env::var_os("NETATMO_HOME_ID")
+            .expect("Environment variable 'NETATMO_HOME_ID' is not set")
+            .to_string_lossy(),
+    );
+
+    let m_params = GetEventsParameters::new(&home_id).size(10);
+
+    let events = NetatmoClient::with_token(&access_token)
+        .get_events(&m_params)
+        .await
+        .expect("Failed to get events");
+
+    println!("{:#?}", events);
+}
@@ -12,7 +12,8 @@ async fn main() {
     let device_id = env::var_os("NETATMO_DEVICE_ID")
         .expect("Environment variable 'NETATMO_DEVICE_ID' is not set")
         .to_string_lossy()
-        .to_string();
+        .parse()
+        .expect("Environment variable 'NETATMO_DEVICE_ID' is not a MAC address");
 
     let homecoachs_data = NetatmoClient::with_token(&access_token)
         .get_homecoachs_data(&device_id)
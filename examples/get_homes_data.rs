@@ -1,5 +1,6 @@
 use netatmo_rs::client::{
     get_homes_data::{GatewayType, GetHomesDataParameters},
+    ids::HomeId,
     NetatmoClient,
 };
 use std::env;
@@ -10,10 +11,11 @@ async fn main() {
         .expect("Environment variable 'NETATMO_ACCESS_TOKEN' is not set.")
         .to_string_lossy()
         .to_string();
-    let home_id = env::var_os("NETATMO_HOME_ID")
-        .expect("Environment variable 'NETATMO_HOME_ID' is not set")
-        .to_string_lossy()
-        .to_string();
+    let home_id = HomeId::new(
+        &env::var_os("NETATMO_HOME_ID")
+            .expect("Environment variable 'NETATMO_HOME_ID' is not set")
+            .to_string_lossy(),
+    );
 
     let m_params = GetHomesDataParameters::new()
         .home_id(&home_id) // to fetch for only one home
@@ -13,7 +13,8 @@ async fn main() {
     let device_id = env::var_os("NETATMO_DEVICE_ID")
         .expect("Environment variable 'NETATMO_DEVICE_ID' is not set")
         .to_string_lossy()
-        .to_string();
+        .parse()
+        .expect("Environment variable 'NETATMO_DEVICE_ID' is not a MAC address");
 
     let m_params = GetMeasureParameters::new(&device_id, Scale::Max, &[Type::Humidity, Type::Temperature, Type::CO2]);
 
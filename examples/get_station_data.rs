@@ -1,4 +1,4 @@
-use netatmo_rs::client::NetatmoClient;
+use netatmo_rs::client::{get_station_data::GetStationDataParameters, NetatmoClient};
 use std::env;
 
 #[tokio::main]
@@ -12,10 +12,11 @@ async fn main() {
     let device_id = env::var_os("NETATMO_DEVICE_ID")
         .expect("Environment variable 'NETATMO_DEVICE_ID' is not set")
         .to_string_lossy()
-        .to_string();
+        .parse()
+        .expect("Environment variable 'NETATMO_DEVICE_ID' is not a MAC address");
 
     let station_data = NetatmoClient::with_token(&access_token)
-        .get_station_data(&device_id)
+        .get_stations_data(&GetStationDataParameters::new().device_id(&device_id))
         .await
         .expect("Failed to get station data");
 
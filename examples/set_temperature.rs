@@ -1,4 +1,5 @@
 use netatmo_rs::client::{
+    ids::{HomeId, RoomId},
     set_room_thermpoint::{Mode, SetRoomThermpointParameters},
     NetatmoClient,
 };
@@ -10,14 +11,16 @@ async fn main() {
         .expect("Environment variable 'NETATMO_ACCESS_TOKEN' is not set.")
         .to_string_lossy()
         .to_string();
-    let home_id = env::var_os("NETATMO_HOME_ID")
-        .expect("Environment variable 'NETATMO_HOME_ID' is not set")
-        .to_string_lossy()
-        .to_string();
-    let room_id = env::var_os("NETATMO_ROOM_ID")
-        .expect("Environment variable 'NETATMO_ROOM_ID' is not set")
-        .to_string_lossy()
-        .to_string();
+    let home_id = HomeId::new(
+        &env::var_os("NETATMO_HOME_ID")
+            .expect("Environment variable 'NETATMO_HOME_ID' is not set")
+            .to_string_lossy(),
+    );
+    let room_id = RoomId::new(
+        &env::var_os("NETATMO_ROOM_ID")
+            .expect("Environment variable 'NETATMO_ROOM_ID' is not set")
+            .to_string_lossy(),
+    );
 
     let m_params = SetRoomThermpointParameters::new(&home_id, &room_id, Mode::Home);
 
@@ -0,0 +1,45 @@
+use crate::{client::NetatmoClient, errors::Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct AddWebhookResponse {
+    pub status: String,
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/security#addwebhook
+pub async fn add_webhook(client: &NetatmoClient, callback_url: &str) -> Result<AddWebhookResponse> {
+    client
+        .call(
+            "add_webhook",
+            "https://api.netatmo.com/api/addwebhook",
+            &parameters(callback_url),
+        )
+        .await
+}
+
+fn parameters(callback_url: &str) -> HashMap<&'static str, String> {
+    let mut params = HashMap::default();
+    params.insert("url", callback_url.to_string());
+
+    params
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod add_webhook {
+        use super::*;
+
+        #[test]
+        fn callback_url_parameter() {
+            let params = parameters("https://example.com/netatmo?token=abc");
+
+            assert_eq!(params.len(), 1);
+            assert_eq!(params["url"], "https://example.com/netatmo?token=abc");
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use super::{
+    get_homecoachs_data::{HealthIndex, HomeCoachDashboard},
+    get_station_data::DashboardData,
+};
+use serde::{Deserialize, Serialize};
+
+// Upper bounds in ppm of the Excellent, Fine, Fair and Poor bands, as colored in the Netatmo app
+const CO2_EXCELLENT: u64 = 800;
+const CO2_FINE: u64 = 1000;
+const CO2_FAIR: u64 = 1500;
+const CO2_POOR: u64 = 2000;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum AirQuality {
+    #[default]
+    Excellent,
+    Fine,
+    Fair,
+    Poor,
+    Unhealthy,
+}
+
+impl AirQuality {
+    pub fn from_co2(ppm: u64) -> Self {
+        match ppm {
+            x if x <= CO2_EXCELLENT => AirQuality::Excellent,
+            x if x <= CO2_FINE => AirQuality::Fine,
+            x if x <= CO2_FAIR => AirQuality::Fair,
+            x if x <= CO2_POOR => AirQuality::Poor,
+            _ => AirQuality::Unhealthy,
+        }
+    }
+}
+
+impl From<HealthIndex> for AirQuality {
+    fn from(health_idx: HealthIndex) -> Self {
+        match health_idx {
+            HealthIndex::Healthy => AirQuality::Excellent,
+            HealthIndex::Fine => AirQuality::Fine,
+            HealthIndex::Fair => AirQuality::Fair,
+            HealthIndex::Poor => AirQuality::Poor,
+            HealthIndex::Unhealthy | HealthIndex::Other(_) => AirQuality::Unhealthy,
+        }
+    }
+}
+
+impl DashboardData {
+    pub fn co2_quality(&self) -> Option<AirQuality> {
+        self.co2.map(AirQuality::from_co2)
+    }
+
+    /// The home coach's overall rating, falling back to the CO2 band for modules without a health index.
+    pub fn air_quality(&self) -> Option<AirQuality> {
+        self.health_idx.map(AirQuality::from).or_else(|| self.co2_quality())
+    }
+}
+
+impl HomeCoachDashboard {
+    pub fn air_quality(&self) -> Option<AirQuality> {
+        self.health_idx
+            .map(AirQuality::from)
+            .or_else(|| self.co2.map(AirQuality::from_co2))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod air_quality {
+        use super::*;
+
+        #[test]
+        fn from_co2() {
+            assert_eq!(AirQuality::from_co2(450), AirQuality::Excellent);
+            assert_eq!(AirQuality::from_co2(1000), AirQuality::Fine);
+            assert_eq!(AirQuality::from_co2(1200), AirQuality::Fair);
+            assert_eq!(AirQuality::from_co2(2001), AirQuality::Unhealthy);
+        }
+    }
+}
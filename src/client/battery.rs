@@ -0,0 +1,100 @@
+use super::{get_home_status::ModuleBatteryState, module_status::ModuleStatus};
+use serde::{Deserialize, Serialize};
+
+// Upper bounds in % of the very low, low, medium and high states, an estimate only used for modules that don't report a
+// known state
+const PERCENT_VERY_LOW: i64 = 10;
+const PERCENT_LOW: i64 = 25;
+const PERCENT_MEDIUM: i64 = 50;
+const PERCENT_HIGH: i64 = 90;
+
+impl ModuleBatteryState {
+    pub fn from_percent(percent: i64) -> Self {
+        match percent {
+            x if x <= PERCENT_VERY_LOW => ModuleBatteryState::VeryLow,
+            x if x <= PERCENT_LOW => ModuleBatteryState::Low,
+            x if x <= PERCENT_MEDIUM => ModuleBatteryState::Medium,
+            x if x <= PERCENT_HIGH => ModuleBatteryState::High,
+            _ => ModuleBatteryState::Full,
+        }
+    }
+
+    pub fn needs_replacement(&self) -> bool {
+        matches!(self, ModuleBatteryState::VeryLow | ModuleBatteryState::Low)
+    }
+}
+
+/// A module's battery as reported through `battery_state` and `battery_percent`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct BatteryAssessment {
+    /// The reported state, or the one estimated from `percent` if the module reported none this crate knows
+    pub state: ModuleBatteryState,
+    pub percent: Option<i64>,
+}
+
+impl BatteryAssessment {
+    pub fn needs_replacement(&self) -> bool {
+        self.state.needs_replacement()
+    }
+}
+
+impl ModuleStatus {
+    /// `None` for modules without batteries, which report neither a state nor a percentage.
+    pub fn battery(&self) -> Option<BatteryAssessment> {
+        let estimated = self.battery_percent().map(ModuleBatteryState::from_percent);
+        let state = match (self.battery_state().cloned(), estimated) {
+            (Some(ModuleBatteryState::Other(_)), Some(estimated)) => estimated,
+            (reported, estimated) => reported.or(estimated)?,
+        };
+
+        Some(BatteryAssessment {
+            state,
+            percent: self.battery_percent(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod battery {
+        use super::*;
+
+        #[test]
+        fn combine_state_and_percent() {
+            let module: ModuleStatus = serde_json::from_str(
+                r#"{"id": "12:34:56:00:00:01", "type": "NXYZ", "firmware_revision": 1, "battery_state": "medium", "battery_percent": 8}"#,
+            )
+            .unwrap();
+            let battery = module.battery().unwrap();
+            assert_eq!(battery.state, ModuleBatteryState::Medium);
+            assert_eq!(battery.percent, Some(8));
+            assert!(!battery.needs_replacement());
+
+            let module: ModuleStatus = serde_json::from_str(
+                r#"{"id": "12:34:56:00:00:04", "type": "NXYZ", "firmware_revision": 1, "battery_state": "something_new", "battery_percent": 8}"#,
+            )
+            .unwrap();
+            assert!(module.battery().unwrap().needs_replacement());
+
+            let module: ModuleStatus = serde_json::from_str(
+                r#"{"id": "12:34:56:00:00:05", "type": "NSD", "firmware_revision": 1, "battery_percent": 40}"#,
+            )
+            .unwrap();
+            assert_eq!(module.battery().unwrap().state, ModuleBatteryState::Medium);
+
+            let module: ModuleStatus = serde_json::from_str(
+                r#"{"id": "12:34:56:00:00:02", "type": "NRV", "firmware_revision": 1, "battery_state": "full"}"#,
+            )
+            .unwrap();
+            assert_eq!(module.battery().unwrap().state, ModuleBatteryState::Full);
+
+            let module: ModuleStatus =
+                serde_json::from_str(r#"{"id": "12:34:56:00:00:03", "type": "NLP", "firmware_revision": 1}"#).unwrap();
+            assert_eq!(module.battery(), None);
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use super::{
+    get_events::{Event, VideoStatus},
+    module_status::CameraStatus,
+    read_response,
+};
+use crate::{
+    client::NetatmoClient,
+    errors::{NetatmoError, Result},
+};
+use bytes::Bytes;
+use log::debug;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// The base url a camera can be reached at, either directly on the LAN or through Netatmo's VPN relay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum CameraUrl {
+    Local(String),
+    Vpn(String),
+}
+
+impl CameraUrl {
+    pub fn base_url(&self) -> &str {
+        match self {
+            CameraUrl::Local(url) | CameraUrl::Vpn(url) => url.trim_end_matches('/'),
+        }
+    }
+
+    pub fn snapshot_url(&self) -> String {
+        format!("{}/live/snapshot_720.jpg", self.base_url())
+    }
+
+    pub fn live_stream_url(&self) -> String {
+        match self {
+            CameraUrl::Local(_) => format!("{}/live/index_local.m3u8", self.base_url()),
+            CameraUrl::Vpn(_) => format!("{}/live/index.m3u8", self.base_url()),
+        }
+    }
+
+    /// The HLS url of a recorded video, e.g. of a doorbell call.
+    pub fn video_url(&self, video_id: &str) -> String {
+        match self {
+            CameraUrl::Local(_) => format!("{}/vod/{}/index_local.m3u8", self.base_url(), video_id),
+            CameraUrl::Vpn(_) => format!("{}/vod/{}/index.m3u8", self.base_url(), video_id),
+        }
+    }
+}
+
+impl CameraStatus {
+    /// The camera's VPN url, as reported by `homestatus`; local access has to be resolved via `resolve_camera_url`.
+    pub fn camera_url(&self) -> Option<CameraUrl> {
+        self.vpn_url.as_ref().map(|x| CameraUrl::Vpn(x.to_string()))
+    }
+}
+
+impl Event {
+    /// The HLS url to play the event's clip back from the camera that recorded it, if the clip can be played.
+    pub fn video_url(&self, camera_url: &CameraUrl) -> Option<String> {
+        match self.video_status {
+            Some(VideoStatus::Available) | Some(VideoStatus::Recording) => {
+                self.video_id.as_ref().map(|x| camera_url.video_url(x))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct CameraPing {
+    pub local_url: String,
+    pub product_name: Option<String>,
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/security#live-stream
+pub async fn ping_camera(client: &NetatmoClient, url: &str) -> Result<CameraPing> {
+    let res = client
+        .http
+        .get(format!("{}/command/ping", url.trim_end_matches('/')))
+        .send()
+        .await
+        .map_err(|_| NetatmoError::FailedToSendRequest)?;
+
+    read_response("ping_camera", res).await
+}
+
+/// Resolves the url to reach a camera at.
+///
+/// As documented by Netatmo, cameras on the same network (`is_local`) are pinged through the VPN to learn their local
+/// url, which is then pinged directly to make sure it's reachable. Any failure falls back to the VPN url.
+pub async fn resolve_camera_url(client: &NetatmoClient, vpn_url: &str, is_local: bool) -> CameraUrl {
+    if !is_local {
+        return CameraUrl::Vpn(vpn_url.to_string());
+    }
+
+    let local_url = match ping_camera(client, vpn_url).await {
+        Ok(ping) => ping.local_url,
+        Err(e) => {
+            debug!("Failed to get local url of camera, using VPN: {}", e);
+            return CameraUrl::Vpn(vpn_url.to_string());
+        }
+    };
+
+    match ping_camera(client, &local_url).await {
+        Ok(ping) if ping.local_url == local_url => CameraUrl::Local(local_url),
+        _ => CameraUrl::Vpn(vpn_url.to_string()),
+    }
+}
+
+/// Like `resolve_camera_url`, but remembers the decision per camera so later calls don't probe again.
+pub async fn cached_camera_url(client: &NetatmoClient, module: &CameraStatus) -> Option<CameraUrl> {
+    let vpn_url = module.vpn_url.as_ref()?;
+    if let Some(url) = client.camera_urls.lock().unwrap().get(&module.id) {
+        return Some(url.clone());
+    }
+
+    let url = resolve_camera_url(client, vpn_url, module.is_local.unwrap_or(false)).await;
+    client
+        .camera_urls
+        .lock()
+        .unwrap()
+        .insert(module.id.clone(), url.clone());
+
+    Some(url)
+}
+
+/// Fetches the camera's live snapshot, preferring its local url.
+///
+/// If the cached local url stopped working, e.g. because the caller left the home network, the decision is forgotten
+/// and the snapshot is fetched through the VPN instead.
+pub async fn camera_snapshot(client: &NetatmoClient, module: &CameraStatus) -> Result<Bytes> {
+    let url = cached_camera_url(client, module)
+        .await
+        .ok_or_else(|| NetatmoError::invalid_parameter("module", "camera has no vpn_url"))?;
+
+    match (fetch_snapshot(client, &url).await, &module.vpn_url) {
+        (Err(e), Some(vpn_url)) if matches!(url, CameraUrl::Local(_)) => {
+            debug!("Failed to fetch snapshot locally, falling back to VPN: {}", e);
+            let vpn_url = CameraUrl::Vpn(vpn_url.to_string());
+            client
+                .camera_urls
+                .lock()
+                .unwrap()
+                .insert(module.id.clone(), vpn_url.clone());
+            fetch_snapshot(client, &vpn_url).await
+        }
+        (res, _) => res,
+    }
+}
+
+async fn fetch_snapshot(client: &NetatmoClient, url: &CameraUrl) -> Result<Bytes> {
+    let res = client
+        .http
+        .get(url.snapshot_url())
+        .send()
+        .await
+        .map_err(|_| NetatmoError::FailedToSendRequest)?;
+
+    match res.status() {
+        StatusCode::OK => res.bytes().await.map_err(|_| NetatmoError::FailedToReadResponse),
+        code => Err(NetatmoError::UnknownApiCallFailure {
+            name: "camera_snapshot".to_string(),
+            status_code: code.as_u16(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{
+        get_events::SecurityEventType,
+        test_util::{events, module_status, server::Server, TIME},
+    };
+
+    mod camera_url {
+        use super::*;
+
+        #[test]
+        fn live_urls() {
+            let vpn = CameraUrl::Vpn("https://prodvpn-eu-2.netatmo.net/restricted/10.255.0.1/abc/".to_string());
+            let local = CameraUrl::Local("http://192.168.0.10/abc".to_string());
+
+            assert_eq!(
+                vpn.snapshot_url(),
+                "https://prodvpn-eu-2.netatmo.net/restricted/10.255.0.1/abc/live/snapshot_720.jpg"
+            );
+            assert_eq!(
+                vpn.live_stream_url(),
+                "https://prodvpn-eu-2.netatmo.net/restricted/10.255.0.1/abc/live/index.m3u8"
+            );
+            assert_eq!(local.live_stream_url(), "http://192.168.0.10/abc/live/index_local.m3u8");
+        }
+
+        #[test]
+        fn event_video_url() {
+            let camera_url = CameraUrl::Vpn("https://prodvpn-eu-2.netatmo.net/restricted/10.255.0.1/abc".to_string());
+            let mut event = Event {
+                video_id: Some("video".to_string()),
+                video_status: Some(VideoStatus::Available),
+                ..events::event("event", SecurityEventType::Movement, TIME)
+            };
+
+            assert_eq!(
+                event.video_url(&camera_url),
+                Some("https://prodvpn-eu-2.netatmo.net/restricted/10.255.0.1/abc/vod/video/index.m3u8".to_string())
+            );
+
+            event.video_status = Some(VideoStatus::Deleted);
+            assert_eq!(event.video_url(&camera_url), None);
+        }
+    }
+
+    mod camera_snapshot {
+        use super::*;
+
+        fn respond(url: &str, path: &str) -> (u16, String) {
+            match path {
+                "/vpn/command/ping" | "/local/command/ping" => (200, format!(r#"{{"local_url": "{}/local"}}"#, url)),
+                "/vpn/live/snapshot_720.jpg" => (200, "vpn".to_string()),
+                _ => (500, String::new()),
+            }
+        }
+
+        #[tokio::test]
+        async fn caches_url() {
+            let server = Server::start(respond).await;
+            let client = NetatmoClient::with_token("token");
+            let camera = module_status::camera("70:ee:50:00:00:01", Some(&format!("{}/vpn", server.url)), true);
+
+            let url = cached_camera_url(&client, &camera).await;
+            assert_eq!(url, Some(CameraUrl::Local(format!("{}/local", server.url))));
+            assert_eq!(cached_camera_url(&client, &camera).await, url);
+            assert_eq!(server.paths(), ["/vpn/command/ping", "/local/command/ping"]);
+        }
+
+        #[tokio::test]
+        async fn falls_back_to_vpn() {
+            let server = Server::start(respond).await;
+            let client = NetatmoClient::with_token("token");
+            let camera = module_status::camera("70:ee:50:00:00:01", Some(&format!("{}/vpn", server.url)), true);
+
+            assert_eq!(camera_snapshot(&client, &camera).await, Ok(Bytes::from("vpn")));
+            assert_eq!(
+                cached_camera_url(&client, &camera).await,
+                Some(CameraUrl::Vpn(format!("{}/vpn", server.url)))
+            );
+            assert_eq!(
+                server.paths(),
+                [
+                    "/vpn/command/ping",
+                    "/local/command/ping",
+                    "/local/live/snapshot_720.jpg",
+                    "/vpn/live/snapshot_720.jpg"
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn missing_vpn_url() {
+            let client = NetatmoClient::with_token("token");
+            let camera = module_status::camera("70:ee:50:00:00:01", None, false);
+
+            assert_eq!(
+                camera_snapshot(&client, &camera).await,
+                Err(NetatmoError::invalid_parameter("module", "camera has no vpn_url"))
+            );
+        }
+    }
+}
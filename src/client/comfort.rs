@@ -0,0 +1,128 @@
+use super::get_station_data::DashboardData;
+use serde::{Deserialize, Serialize};
+
+// Magnus formula coefficients, cf. Sonntag 1990
+const MAGNUS_A: f64 = 17.62;
+const MAGNUS_B: f64 = 243.12;
+
+/// Dew point in °C for a temperature in °C and a relative humidity in %.
+pub fn dew_point(temperature: f64, humidity: f64) -> f64 {
+    let gamma = (humidity / 100.0).ln() + MAGNUS_A * temperature / (MAGNUS_B + temperature);
+    MAGNUS_B * gamma / (MAGNUS_A - gamma)
+}
+
+/// Heat index in °C as computed by the US National Weather Service, for a temperature in °C and a relative humidity
+/// in %.
+pub fn heat_index(temperature: f64, humidity: f64) -> f64 {
+    let t = temperature * 9.0 / 5.0 + 32.0;
+    let rh = humidity;
+
+    let simple = 0.5 * (t + 61.0 + (t - 68.0) * 1.2 + rh * 0.094);
+    let hi = if (simple + t) / 2.0 < 80.0 {
+        simple
+    } else {
+        let mut hi = -42.379 + 2.04901523 * t + 10.14333127 * rh
+            - 0.22475541 * t * rh
+            - 0.00683783 * t * t
+            - 0.05481717 * rh * rh
+            + 0.00122874 * t * t * rh
+            + 0.00085282 * t * rh * rh
+            - 0.00000199 * t * t * rh * rh;
+        if rh < 13.0 && (80.0..=112.0).contains(&t) {
+            hi -= (13.0 - rh) / 4.0 * ((17.0 - (t - 95.0).abs()) / 17.0).sqrt();
+        } else if rh > 85.0 && (80.0..=87.0).contains(&t) {
+            hi += (rh - 85.0) / 10.0 * (87.0 - t) / 5.0;
+        }
+        hi
+    };
+
+    (hi - 32.0) * 5.0 / 9.0
+}
+
+/// Humidex as used by Environment Canada, for a temperature in °C and a relative humidity in %.
+pub fn humidex(temperature: f64, humidity: f64) -> f64 {
+    let dew_point = dew_point(temperature, humidity) + 273.15;
+    let vapour_pressure = 6.11 * (5417.7530 * (1.0 / 273.16 - 1.0 / dew_point)).exp();
+    temperature + 0.5555 * (vapour_pressure - 10.0)
+}
+
+/// The index a user chose to show how warm it feels, cf. `feel_like_algorithm`. Unknown preferences are kept as
+/// `Other` and computed as humidex.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "i64", into = "i64")]
+#[non_exhaustive]
+pub enum FeelLikeAlgorithm {
+    #[default]
+    Humidex,
+    HeatIndex,
+    Other(i64),
+}
+
+impl FeelLikeAlgorithm {
+    pub fn from_preference(preference: i64) -> Option<Self> {
+        match preference {
+            0 => Some(FeelLikeAlgorithm::Humidex),
+            1 => Some(FeelLikeAlgorithm::HeatIndex),
+            _ => None,
+        }
+    }
+
+    /// How warm it feels in °C, for a temperature in °C and a relative humidity in %.
+    pub fn feels_like(&self, temperature: f64, humidity: f64) -> f64 {
+        match self {
+            FeelLikeAlgorithm::Humidex | FeelLikeAlgorithm::Other(_) => humidex(temperature, humidity),
+            FeelLikeAlgorithm::HeatIndex => heat_index(temperature, humidity),
+        }
+    }
+}
+
+impl From<i64> for FeelLikeAlgorithm {
+    fn from(preference: i64) -> Self {
+        FeelLikeAlgorithm::from_preference(preference).unwrap_or(FeelLikeAlgorithm::Other(preference))
+    }
+}
+
+impl From<FeelLikeAlgorithm> for i64 {
+    fn from(algorithm: FeelLikeAlgorithm) -> Self {
+        match algorithm {
+            FeelLikeAlgorithm::Humidex => 0,
+            FeelLikeAlgorithm::HeatIndex => 1,
+            FeelLikeAlgorithm::Other(preference) => preference,
+        }
+    }
+}
+
+impl DashboardData {
+    pub fn dew_point(&self) -> Option<f64> {
+        Some(dew_point(self.temperature?, self.humidity? as f64))
+    }
+
+    pub fn heat_index(&self) -> Option<f64> {
+        Some(heat_index(self.temperature?, self.humidity? as f64))
+    }
+
+    pub fn humidex(&self) -> Option<f64> {
+        Some(humidex(self.temperature?, self.humidity? as f64))
+    }
+
+    pub fn feels_like(&self, algorithm: FeelLikeAlgorithm) -> Option<f64> {
+        Some(algorithm.feels_like(self.temperature?, self.humidity? as f64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod comfort {
+        use super::*;
+
+        #[test]
+        fn known_values() {
+            assert!((dew_point(25.0, 60.0) - 16.69).abs() < 0.05);
+            assert!((heat_index(32.0, 70.0) - 40.4).abs() < 0.1);
+            assert!((humidex(30.0, 70.0) - 41.2).abs() < 0.3);
+        }
+    }
+}
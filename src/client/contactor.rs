@@ -0,0 +1,119 @@
+use super::{
+    get_home_status::{get_home_status, GetHomeStatusParameters},
+    ids::{HomeId, ModuleId},
+    module_status::{ModuleStatus, SwitchStatus},
+    set_state::{set_state, ModuleState, SetStateParameters},
+};
+use crate::{
+    client::NetatmoClient,
+    errors::{NetatmoError, Result},
+};
+use std::{future::Future, time::Duration};
+
+// The gateway takes a moment to relay a command and report the module's new state
+const READ_BACK_ATTEMPTS: usize = 3;
+const READ_BACK_INTERVAL: Duration = Duration::from_secs(2);
+
+impl SwitchStatus {
+    /// Whether a contactor (NLPO, NLC), e.g. driving a garage door or gate, is closed, if it reported it.
+    pub fn contactor_closed(&self) -> Option<bool> {
+        self.on
+    }
+}
+
+/// Switches a contactor paired with the gateway `bridge` and reads back its state.
+///
+/// A door or gate that silently failed to move must not go unnoticed, so this fails with `StateNotConfirmed` unless
+/// the module reports the requested state within a few seconds.
+pub async fn set_contactor(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    module_id: &ModuleId,
+    bridge: &ModuleId,
+    on: bool,
+) -> Result<()> {
+    let parameters = SetStateParameters::new(home_id, &[ModuleState::new(module_id).bridge(bridge).on(on)]);
+    set_state(client, &parameters).await?;
+
+    confirm_state(module_id, on, READ_BACK_ATTEMPTS, READ_BACK_INTERVAL, || async {
+        let status = get_home_status(client, &GetHomeStatusParameters::new().home_id(home_id)).await?;
+        Ok(status
+            .body
+            .home
+            .modules
+            .unwrap_or_default()
+            .into_iter()
+            .find(|x| x.id() == module_id)
+            .and_then(|x| match x {
+                ModuleStatus::Switch(x) => x.contactor_closed(),
+                _ => None,
+            }))
+    })
+    .await
+}
+
+/// Reads the state of a module with `read` up to `attempts` times, waiting `interval` before each, until it reports
+/// `on`.
+async fn confirm_state<F, Fut>(
+    module_id: &ModuleId,
+    on: bool,
+    attempts: usize,
+    interval: Duration,
+    mut read: F,
+) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<bool>>>,
+{
+    for _ in 0..attempts {
+        tokio::time::sleep(interval).await;
+        if read().await? == Some(on) {
+            return Ok(());
+        }
+    }
+
+    Err(NetatmoError::StateNotConfirmed {
+        module_id: module_id.clone(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::RefCell;
+
+    mod confirm_state {
+        use super::*;
+
+        async fn confirm(on: bool, reports: &[Option<bool>]) -> (Result<()>, usize) {
+            let reads = RefCell::new(0);
+            let result = confirm_state(&ModuleId::new("12:34:56:00:00:01"), on, 3, Duration::ZERO, || {
+                let report = reports[*reads.borrow()];
+                *reads.borrow_mut() += 1;
+                async move { Ok(report) }
+            })
+            .await;
+
+            (result, reads.into_inner())
+        }
+
+        #[tokio::test]
+        async fn confirmed() {
+            assert_eq!(confirm(true, &[None, Some(false), Some(true)]).await, (Ok(()), 3));
+            assert_eq!(confirm(false, &[Some(false)]).await, (Ok(()), 1));
+        }
+
+        #[tokio::test]
+        async fn not_confirmed() {
+            let (result, reads) = confirm(true, &[Some(false), None, Some(false)]).await;
+
+            assert_eq!(
+                result,
+                Err(NetatmoError::StateNotConfirmed {
+                    module_id: ModuleId::new("12:34:56:00:00:01")
+                })
+            );
+            assert_eq!(reads, 3);
+        }
+    }
+}
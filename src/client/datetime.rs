@@ -0,0 +1,36 @@
+use super::time_series::{Point, TimeSeries};
+use chrono::{DateTime, Utc};
+
+/// Converts seconds since the epoch, as all APIs report them, into a UTC date and time.
+pub fn from_epoch(seconds: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(seconds, 0)
+}
+
+impl Point<'_> {
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        from_epoch(self.timestamp)
+    }
+}
+
+impl TimeSeries {
+    pub fn datetimes(&self) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        self.timestamps().filter_map(from_epoch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::client::{get_events::SecurityEventType, test_util::events};
+
+    mod epoch {
+        use super::*;
+
+        #[test]
+        fn utc() {
+            let event = events::event("event", SecurityEventType::Movement, 1689865621);
+
+            assert_eq!(event.time.to_rfc3339(), "2023-07-20T15:07:01+00:00");
+            assert_eq!(serde_json::to_value(&event).unwrap()["time"], 1689865621);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use super::get_events::{Event, SecurityEventType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum DoorbellCall {
+    Incoming,
+    Accepted,
+    Missed,
+}
+
+impl Event {
+    /// Interprets the event as a call on a Smart Video Doorbell (NDB).
+    pub fn doorbell_call(&self) -> Option<DoorbellCall> {
+        match self.type_field {
+            SecurityEventType::IncomingCall => Some(DoorbellCall::Incoming),
+            SecurityEventType::AcceptedCall => Some(DoorbellCall::Accepted),
+            SecurityEventType::MissedCall => Some(DoorbellCall::Missed),
+            _ => None,
+        }
+    }
+
+    /// The direct url of the event's snapshot, falling back to the first subevent's one.
+    pub fn snapshot_url(&self) -> Option<&str> {
+        self.snapshot.as_ref().and_then(|x| x.url.as_deref()).or_else(|| {
+            self.subevents
+                .as_ref()
+                .and_then(|x| x.first())
+                .and_then(|x| x.snapshot.as_ref())
+                .and_then(|x| x.url.as_deref())
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod doorbell_call {
+        use super::*;
+
+        #[test]
+        fn parse_missed_call() {
+            let json = r#"{
+                "id": "...",
+                "type": "missed_call",
+                "time": 1689865621,
+                "module_id": "70:ee:50:00:00:04",
+                "video_id": "...",
+                "video_status": "available",
+                "snapshot": {
+                  "url": "https://netatmocameraimage.blob.core.windows.net/production/..."
+                }
+              }"#;
+
+            let event: Event = serde_json::from_str(json).unwrap();
+
+            assert_eq!(event.doorbell_call(), Some(DoorbellCall::Missed));
+            assert!(event.snapshot_url().is_some());
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{
+    client::NetatmoClient,
+    errors::{NetatmoError, Result},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+pub struct DropWebhookParameters {
+    app_type: Option<AppType>,
+}
+
+impl DropWebhookParameters {
+    pub fn new() -> Self {
+        DropWebhookParameters::default()
+    }
+
+    pub fn app_type(self, app_type: AppType) -> Self {
+        DropWebhookParameters {
+            app_type: Some(app_type),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+#[non_exhaustive]
+pub enum AppType {
+    #[default]
+    Security,
+}
+
+impl fmt::Display for AppType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            AppType::Security => "app_security",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for AppType {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "app_security" => Ok(AppType::Security),
+            _ => Err(NetatmoError::invalid_parameter(
+                "app_type",
+                &format!("unknown value '{}'", s),
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for AppType {
+    type Error = NetatmoError;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<AppType> for String {
+    fn from(app_type: AppType) -> Self {
+        app_type.to_string()
+    }
+}
+
+#[allow(clippy::implicit_hasher)]
+impl From<&DropWebhookParameters> for HashMap<&'static str, String> {
+    fn from(p: &DropWebhookParameters) -> HashMap<&'static str, String> {
+        let mut map = HashMap::default();
+        if let Some(app_type) = &p.app_type {
+            map.insert("app_type", app_type.to_string());
+        }
+
+        map
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct DropWebhookResponse {
+    pub status: String,
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/security#dropwebhook
+pub async fn drop_webhook(client: &NetatmoClient, parameters: &DropWebhookParameters) -> Result<DropWebhookResponse> {
+    let params: HashMap<&str, String> = parameters.into();
+
+    client
+        .call("drop_webhook", "https://api.netatmo.com/api/dropwebhook", &params)
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::assert_round_trip;
+
+    mod drop_webhook {
+        use super::*;
+
+        #[test]
+        fn app_type_round_trip() {
+            assert_round_trip!(AppType::Security);
+        }
+
+        #[test]
+        fn parameters() {
+            let default: HashMap<&str, String> = (&DropWebhookParameters::new()).into();
+            let security: HashMap<&str, String> = (&DropWebhookParameters::new().app_type(AppType::Security)).into();
+
+            assert!(default.is_empty());
+            assert_eq!(security.len(), 1);
+            assert_eq!(security["app_type"], "app_security");
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use super::{
+    get_events::{Event, SecurityEventType},
+    ids::PersonId,
+};
+
+/// Client side filter for events, for the criteria `getevents` can't filter on itself.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct EventFilter {
+    event_types: Option<Vec<SecurityEventType>>,
+    person_ids: Option<Vec<PersonId>>,
+    exclude_daily_summaries: bool,
+}
+
+impl EventFilter {
+    pub fn new() -> Self {
+        EventFilter::default()
+    }
+
+    /// Keeps events of the given types; outdoor events match if one of their subevents does.
+    pub fn event_types(self, event_types: &[SecurityEventType]) -> Self {
+        EventFilter {
+            event_types: Some(event_types.to_vec()),
+            ..self
+        }
+    }
+
+    pub fn person_ids(self, person_ids: &[PersonId]) -> Self {
+        EventFilter {
+            person_ids: Some(person_ids.to_vec()),
+            ..self
+        }
+    }
+
+    pub fn exclude_daily_summaries(self) -> Self {
+        EventFilter {
+            exclude_daily_summaries: true,
+            ..self
+        }
+    }
+
+    pub fn humans_only() -> Self {
+        EventFilter::new().event_types(&[
+            SecurityEventType::Person,
+            SecurityEventType::PersonAway,
+            SecurityEventType::Human,
+        ])
+    }
+
+    pub fn vehicles_only() -> Self {
+        EventFilter::new().event_types(&[SecurityEventType::Vehicle])
+    }
+
+    pub fn animals_only() -> Self {
+        EventFilter::new().event_types(&[SecurityEventType::Animal])
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        if self.exclude_daily_summaries && event.type_field == SecurityEventType::DailySummary {
+            return false;
+        }
+        if let Some(event_types) = &self.event_types {
+            if !event_types.iter().any(|x| event.has_type(x)) {
+                return false;
+            }
+        }
+        if let Some(person_ids) = &self.person_ids {
+            if !event.person_id.as_ref().is_some_and(|x| person_ids.contains(x)) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    pub fn apply(&self, events: Vec<Event>) -> Vec<Event> {
+        events.into_iter().filter(|x| self.matches(x)).collect()
+    }
+}
+
+impl Event {
+    /// Whether the event or one of its subevents is of the given type.
+    pub fn has_type(&self, event_type: &SecurityEventType) -> bool {
+        self.type_field == *event_type
+            || self
+                .subevents
+                .as_ref()
+                .is_some_and(|x| x.iter().any(|x| x.type_field == *event_type))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::{events, TIME};
+
+    mod event_filter {
+        use super::*;
+
+        #[test]
+        fn vehicles_only_matches_subevents() {
+            let outdoor = Event {
+                subevents: Some(vec![events::sub_event("sub", SecurityEventType::Vehicle, TIME)]),
+                ..events::event("outdoor", SecurityEventType::Outdoor, TIME)
+            };
+            let movement = events::event("movement", SecurityEventType::Movement, TIME);
+
+            let events = EventFilter::vehicles_only().apply(vec![outdoor.clone(), movement]);
+
+            assert_eq!(events, vec![outdoor]);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use super::{
+    general_err_handler,
+    get_camera_picture::get_camera_picture,
+    get_events::{Event, Snapshot},
+};
+use crate::{
+    client::NetatmoClient,
+    errors::{NetatmoError, Result},
+};
+use bytes::Bytes;
+use reqwest::StatusCode;
+
+/// Downloads the picture a snapshot or vignette refers to.
+///
+/// Pictures with a direct url are fetched from it, others through `getcamerapicture`. Pictures which aren't available
+/// anymore are reported as `NetatmoError::MediaExpired`.
+pub async fn download_picture(client: &NetatmoClient, picture: &Snapshot) -> Result<Option<Bytes>> {
+    if let Some(url) = &picture.url {
+        return download_url(client, url).await.map(Some);
+    }
+
+    match (&picture.id, &picture.key) {
+        (Some(id), Some(key)) => get_camera_picture(client, id, key).await.map(Some).map_err(media_error),
+        _ => Ok(None),
+    }
+}
+
+async fn download_url(client: &NetatmoClient, url: &str) -> Result<Bytes> {
+    let res = client
+        .http
+        .get(url)
+        .send()
+        .await
+        .map_err(|_| NetatmoError::FailedToSendRequest)?;
+
+    let res = general_err_handler(res, "download_picture".to_string(), StatusCode::OK)
+        .await
+        .map_err(media_error)?;
+    res.bytes().await.map_err(|_| NetatmoError::FailedToReadResponse)
+}
+
+// Error code the API answers with in the body for pictures whose key expired or was deleted,
+// cf. https://dev.netatmo.com/apidocumentation/general#status-ok
+const NOT_FOUND_CODE: isize = 9;
+
+/// Reports a picture that isn't available anymore as `MediaExpired`, whether the API answered with a bare status
+/// code or with an error body. Forbidden answers are kept as they are, as they point at a missing scope rather than
+/// at a gone picture.
+fn media_error(e: NetatmoError) -> NetatmoError {
+    match e {
+        NetatmoError::UnknownApiCallFailure { status_code, .. }
+            if status_code == StatusCode::NOT_FOUND.as_u16() || status_code == StatusCode::GONE.as_u16() =>
+        {
+            NetatmoError::MediaExpired
+        }
+        NetatmoError::ApiCallFailed { code, .. } if code == NOT_FOUND_CODE => NetatmoError::MediaExpired,
+        e => e,
+    }
+}
+
+/// Downloads the event's snapshot, or the first subevent's one for outdoor events.
+pub async fn download_event_snapshot(client: &NetatmoClient, event: &Event) -> Result<Option<Bytes>> {
+    let snapshot = event.snapshot.as_ref().or_else(|| {
+        event
+            .subevents
+            .as_ref()
+            .and_then(|x| x.first())
+            .and_then(|x| x.snapshot.as_ref())
+    });
+    match snapshot {
+        Some(snapshot) => download_picture(client, snapshot).await,
+        None => Ok(None),
+    }
+}
+
+/// Downloads the event's vignette, or the first subevent's one for outdoor events.
+pub async fn download_event_vignette(client: &NetatmoClient, event: &Event) -> Result<Option<Bytes>> {
+    let vignette = event.vignette.as_ref().or_else(|| {
+        event
+            .subevents
+            .as_ref()
+            .and_then(|x| x.first())
+            .and_then(|x| x.vignette.as_ref())
+    });
+    match vignette {
+        Some(vignette) => download_picture(client, vignette).await,
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod media_error {
+        use super::*;
+
+        fn status(status_code: u16) -> NetatmoError {
+            NetatmoError::UnknownApiCallFailure {
+                name: "download_picture".to_string(),
+                status_code,
+            }
+        }
+
+        fn body(code: isize) -> NetatmoError {
+            NetatmoError::ApiCallFailed {
+                name: "get_camera_picture".to_string(),
+                code,
+                msg: "...".to_string(),
+            }
+        }
+
+        #[test]
+        fn expired_by_status_code() {
+            assert_eq!(media_error(status(404)), NetatmoError::MediaExpired);
+            assert_eq!(media_error(status(410)), NetatmoError::MediaExpired);
+            assert_eq!(media_error(status(503)), status(503));
+        }
+
+        #[test]
+        fn expired_by_error_body() {
+            assert_eq!(media_error(body(9)), NetatmoError::MediaExpired);
+            assert_eq!(media_error(body(26)), body(26));
+        }
+
+        #[test]
+        fn forbidden_is_kept() {
+            assert_eq!(media_error(status(403)), status(403));
+            assert_eq!(media_error(body(13)), body(13));
+        }
+    }
+}
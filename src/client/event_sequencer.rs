@@ -0,0 +1,99 @@
+use super::{get_events::Event, timestamp::Timestamp};
+use std::collections::{BTreeMap, HashMap};
+
+/// Deduplicates events by id and releases them in chronological order.
+///
+/// Events are held back for `lookback` seconds so late arrivals, e.g. a polled page overlapping webhook pushes, can be
+/// sorted in. Events older than the last released one are dropped to keep the output monotonic.
+#[derive(Debug, Clone)]
+pub struct EventSequencer {
+    lookback: i64,
+    seen: HashMap<String, i64>,
+    pending: BTreeMap<(i64, String), Event>,
+    watermark: Option<i64>,
+}
+
+impl EventSequencer {
+    pub fn new(lookback: i64) -> Self {
+        EventSequencer {
+            lookback,
+            seen: HashMap::new(),
+            pending: BTreeMap::new(),
+            watermark: None,
+        }
+    }
+
+    /// Adds an event, returns whether it was accepted, i.e. is neither a duplicate nor too late.
+    pub fn push(&mut self, event: Event) -> bool {
+        let time = event.time.epoch_seconds();
+        if self.seen.contains_key(&event.id) || self.watermark.is_some_and(|x| time < x) {
+            return false;
+        }
+
+        self.seen.insert(event.id.clone(), time);
+        self.pending.insert((time, event.id.clone()), event);
+        true
+    }
+
+    /// Releases, in order, the events that happened at least `lookback` seconds before `now`.
+    pub fn release(&mut self, now: i64) -> Vec<Event> {
+        self.release_until(now - self.lookback)
+    }
+
+    /// Releases all pending events regardless of the lookback window.
+    pub fn flush(&mut self) -> Vec<Event> {
+        self.release_until(i64::MAX)
+    }
+
+    fn release_until(&mut self, until: i64) -> Vec<Event> {
+        let still_pending = self.pending.split_off(&(until.saturating_add(1), String::new()));
+        let released: Vec<Event> = std::mem::replace(&mut self.pending, still_pending)
+            .into_values()
+            .collect();
+
+        if let Some(last) = released.last() {
+            let horizon = last.time.epoch_seconds();
+            self.watermark = Some(horizon);
+            // Ids only need to be remembered as long as an event with them could still be accepted
+            self.seen.retain(|_, time| *time >= horizon);
+        }
+
+        released
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{get_events::SecurityEventType, test_util::events};
+
+    mod event_sequencer {
+        use super::*;
+
+        fn event(id: &str, time: i64) -> Event {
+            events::event(id, SecurityEventType::Movement, time)
+        }
+
+        #[test]
+        fn dedups_and_orders() {
+            let mut sequencer = EventSequencer::new(60);
+
+            assert!(sequencer.push(event("b", 120)));
+            assert!(sequencer.push(event("a", 100)));
+            assert!(!sequencer.push(event("a", 100)));
+            assert!(sequencer.push(event("c", 200)));
+
+            let released = sequencer.release(180);
+            assert_eq!(
+                released.iter().map(|x| x.id.as_str()).collect::<Vec<_>>(),
+                vec!["a", "b"]
+            );
+
+            assert!(!sequencer.push(event("late", 110)));
+            assert_eq!(
+                sequencer.flush().iter().map(|x| x.id.as_str()).collect::<Vec<_>>(),
+                vec!["c"]
+            );
+        }
+    }
+}
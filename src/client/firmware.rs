@@ -0,0 +1,118 @@
+use super::{
+    get_home_status::HomeStatus, get_homecoachs_data::HomeCoach, get_station_data, ids::ModuleId,
+    module_type::ModuleType,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+impl get_station_data::Device {
+    pub fn firmware_revision(&self) -> i64 {
+        self.firmware
+    }
+}
+
+impl get_station_data::Module {
+    pub fn firmware_revision(&self) -> i64 {
+        self.firmware
+    }
+}
+
+impl HomeCoach {
+    pub fn firmware_revision(&self) -> i64 {
+        self.firmware
+    }
+}
+
+/// The modules of one type running the same firmware.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct FirmwareGroup {
+    pub module_type: ModuleType,
+    pub firmware_revision: i64,
+    /// The newest firmware any module of the same type in the home runs
+    pub latest_revision: i64,
+    pub module_ids: Vec<ModuleId>,
+}
+
+impl FirmwareGroup {
+    pub fn is_outdated(&self) -> bool {
+        self.firmware_revision < self.latest_revision
+    }
+}
+
+impl HomeStatus {
+    /// Groups the home's modules by type and firmware, ordered by type and oldest firmware first.
+    pub fn firmware_report(&self) -> Vec<FirmwareGroup> {
+        let modules = self.body.home.modules.iter().flatten();
+
+        let mut latest: HashMap<&ModuleType, i64> = HashMap::new();
+        for module in modules.clone() {
+            let revision = latest.entry(module.module_type()).or_insert(module.firmware_revision());
+            *revision = (*revision).max(module.firmware_revision());
+        }
+
+        let mut groups: Vec<FirmwareGroup> = vec![];
+        for module in modules {
+            match groups
+                .iter_mut()
+                .find(|x| &x.module_type == module.module_type() && x.firmware_revision == module.firmware_revision())
+            {
+                Some(group) => group.module_ids.push(module.id().clone()),
+                None => groups.push(FirmwareGroup {
+                    module_type: module.module_type().clone(),
+                    firmware_revision: module.firmware_revision(),
+                    latest_revision: latest[module.module_type()],
+                    module_ids: vec![module.id().clone()],
+                }),
+            }
+        }
+
+        groups.sort_by(|a, b| {
+            a.module_type
+                .to_string()
+                .cmp(&b.module_type.to_string())
+                .then(a.firmware_revision.cmp(&b.firmware_revision))
+        });
+        groups
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod firmware_report {
+        use super::*;
+
+        #[test]
+        fn group_by_type_and_revision() {
+            let json = r#"{
+                "status": "ok",
+                "time_server": 1689865621,
+                "body": {
+                  "home": {
+                    "id": "home",
+                    "modules": [
+                      {"id": "12:34:56:00:00:01", "type": "NRV", "firmware_revision": 79},
+                      {"id": "12:34:56:00:00:02", "type": "NRV", "firmware_revision": 100},
+                      {"id": "12:34:56:00:00:03", "type": "NRV", "firmware_revision": 79},
+                      {"id": "12:34:56:00:00:04", "type": "NAPlug", "firmware_revision": 240}
+                    ]
+                  }
+                }
+              }"#;
+            let home_status: HomeStatus = serde_json::from_str(json).unwrap();
+
+            let report = home_status.firmware_report();
+
+            assert_eq!(report.len(), 3);
+            assert_eq!(report[0].module_type, ModuleType::NAPlug);
+            assert!(!report[0].is_outdated());
+            assert_eq!(report[1].firmware_revision, 79);
+            assert_eq!(report[1].module_ids, vec!["12:34:56:00:00:01", "12:34:56:00:00:03"]);
+            assert!(report[1].is_outdated());
+            assert!(!report[2].is_outdated());
+        }
+    }
+}
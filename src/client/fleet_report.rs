@@ -0,0 +1,193 @@
+use super::{
+    get_station_data::StationData, module_type::ModuleType, reachability::Reachability, signal::SignalQuality,
+    timestamp::Timestamp,
+};
+use serde::{Deserialize, Serialize};
+
+// Battery percentages at or below which a module is flagged
+const BATTERY_CRITICAL: u64 = 10;
+const BATTERY_LOW: u64 = 25;
+// Seconds without a message after which a module is considered stale, modules report every 5 minutes
+const STALE_AFTER: i64 = 60 * 60;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Urgency {
+    #[default]
+    None,
+    Low,
+    High,
+    Critical,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum ModuleIssue {
+    Unreachable,
+    BatteryCritical,
+    BatteryLow,
+    Stale,
+    WeakSignal,
+}
+
+impl ModuleIssue {
+    pub fn urgency(&self) -> Urgency {
+        match self {
+            ModuleIssue::Unreachable | ModuleIssue::BatteryCritical => Urgency::Critical,
+            ModuleIssue::BatteryLow | ModuleIssue::Stale => Urgency::High,
+            ModuleIssue::WeakSignal => Urgency::Low,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct ModuleReport {
+    pub station_id: String,
+    pub station_name: String,
+    pub module_id: String,
+    pub module_name: String,
+    pub module_type: ModuleType,
+    pub battery_percent: u64,
+    pub rf_status: u64,
+    pub seconds_since_seen: i64,
+    pub issues: Vec<ModuleIssue>,
+}
+
+impl ModuleReport {
+    pub fn urgency(&self) -> Urgency {
+        self.issues.iter().map(ModuleIssue::urgency).max().unwrap_or_default()
+    }
+}
+
+impl StationData {
+    /// Reports on the battery and connectivity of all battery powered modules at `now`, most urgent first.
+    pub fn fleet_report(&self, now: i64) -> Vec<ModuleReport> {
+        let mut reports: Vec<ModuleReport> = self
+            .body
+            .devices
+            .iter()
+            .flat_map(|device| device.modules.iter().map(move |module| (device, module)))
+            .map(|(device, module)| {
+                let seconds_since_seen = (now - module.last_seen.epoch_seconds()).max(0);
+                let mut issues = vec![];
+                match module.reachability(now, STALE_AFTER) {
+                    Reachability::Unreachable => issues.push(ModuleIssue::Unreachable),
+                    Reachability::Stale { .. } => issues.push(ModuleIssue::Stale),
+                    _ => {}
+                }
+                if module.battery_percent <= BATTERY_CRITICAL {
+                    issues.push(ModuleIssue::BatteryCritical);
+                } else if module.battery_percent <= BATTERY_LOW {
+                    issues.push(ModuleIssue::BatteryLow);
+                }
+                if module.rf_quality() == SignalQuality::Bad {
+                    issues.push(ModuleIssue::WeakSignal);
+                }
+
+                ModuleReport {
+                    station_id: device.id.clone(),
+                    station_name: device.station_name.clone(),
+                    module_id: module.id.clone(),
+                    module_name: module.module_name.clone(),
+                    module_type: module.type_info.clone(),
+                    battery_percent: module.battery_percent,
+                    rf_status: module.rf_status,
+                    seconds_since_seen,
+                    issues,
+                }
+            })
+            .collect();
+
+        reports.sort_by(|a, b| {
+            b.urgency()
+                .cmp(&a.urgency())
+                .then(a.battery_percent.cmp(&b.battery_percent))
+        });
+        reports
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::TIME;
+    use serde_json::{json, Value};
+
+    mod fleet_report {
+        use super::*;
+
+        fn module(id: &str, battery_percent: u64, rf_status: u64, reachable: bool, last_seen: i64) -> Value {
+            json!({
+                "_id": id,
+                "battery_percent": battery_percent,
+                "battery_vp": 5000,
+                "dashboard_data": {"time_utc": last_seen},
+                "data_type": ["Temperature", "Humidity"],
+                "firmware": 50,
+                "last_message": last_seen,
+                "last_seen": last_seen,
+                "last_setup": 1556295333,
+                "module_name": id,
+                "reachable": reachable,
+                "rf_status": rf_status,
+                "type": "NAModule1"
+            })
+        }
+
+        #[test]
+        fn most_urgent_first() {
+            let station_data: StationData = serde_json::from_value(json!({
+                "body": {
+                    "devices": [{
+                        "_id": "70:ee:50:00:00:20",
+                        "dashboard_data": {"time_utc": TIME},
+                        "data_type": ["Temperature"],
+                        "modules": [
+                            module("02:00:00:00:00:01", 100, 60, true, TIME - 300),
+                            module("02:00:00:00:00:02", 80, 60, false, TIME - 300),
+                            module("02:00:00:00:00:03", 50, 60, true, TIME - 2 * 3600),
+                            module("02:00:00:00:00:04", 20, 95, true, TIME - 300),
+                            module("02:00:00:00:00:05", 8, 60, true, TIME - 300)
+                        ],
+                        "place": {"altitude": 50, "city": "Paris", "country": "FR", "location": [2.35, 48.85], "timezone": "Europe/Paris"},
+                        "reachable": true,
+                        "station_name": "Home",
+                        "type": "NAMain"
+                    }],
+                    "user": {
+                        "administrative": {"feel_like_algo": 0, "lang": "en-US", "reg_locale": "en-US", "unit": 0, "windunit": 0},
+                        "mail": "user@example.com"
+                    }
+                },
+                "status": "ok",
+                "time_exec": 0.1,
+                "time_server": TIME
+            }))
+            .unwrap();
+
+            let report = station_data.fleet_report(TIME);
+
+            assert_eq!(
+                report
+                    .iter()
+                    .map(|x| (x.module_id.as_str(), x.issues.clone()))
+                    .collect::<Vec<_>>(),
+                [
+                    ("02:00:00:00:00:05", vec![ModuleIssue::BatteryCritical]),
+                    ("02:00:00:00:00:02", vec![ModuleIssue::Unreachable]),
+                    (
+                        "02:00:00:00:00:04",
+                        vec![ModuleIssue::BatteryLow, ModuleIssue::WeakSignal]
+                    ),
+                    ("02:00:00:00:00:03", vec![ModuleIssue::Stale]),
+                    ("02:00:00:00:00:01", vec![]),
+                ]
+            );
+            assert_eq!(report[2].urgency(), Urgency::High);
+            assert_eq!(report[3].seconds_since_seen, 2 * 3600);
+        }
+    }
+}
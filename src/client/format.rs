@@ -0,0 +1,146 @@
+use super::{
+    get_homes_data::User,
+    get_station_data::Administrative,
+    units::{Pressure, PressureUnit, WindSpeed, WindUnit},
+};
+use serde::{Deserialize, Serialize};
+
+/// The unit system a user chose for temperatures and rain, cf. `unit_system`. Unknown preferences are kept as
+/// `Other` and formatted as metric.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "i64", into = "i64")]
+#[non_exhaustive]
+pub enum UnitSystem {
+    #[default]
+    Metric,
+    Imperial,
+    Other(i64),
+}
+
+impl UnitSystem {
+    pub fn from_preference(preference: i64) -> Option<Self> {
+        match preference {
+            0 => Some(UnitSystem::Metric),
+            1 => Some(UnitSystem::Imperial),
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for UnitSystem {
+    fn from(preference: i64) -> Self {
+        UnitSystem::from_preference(preference).unwrap_or(UnitSystem::Other(preference))
+    }
+}
+
+impl From<UnitSystem> for i64 {
+    fn from(unit_system: UnitSystem) -> Self {
+        match unit_system {
+            UnitSystem::Metric => 0,
+            UnitSystem::Imperial => 1,
+            UnitSystem::Other(preference) => preference,
+        }
+    }
+}
+
+/// Renders measurements the way the Netatmo app shows them to a user.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formatter {
+    pub unit_system: UnitSystem,
+    pub wind_unit: WindUnit,
+    pub pressure_unit: PressureUnit,
+}
+
+impl Formatter {
+    pub fn for_user(user: &User) -> Self {
+        Formatter {
+            unit_system: user.unit_system,
+            wind_unit: user.unit_wind,
+            pressure_unit: user.unit_pressure,
+        }
+    }
+
+    pub fn for_administrative(administrative: &Administrative) -> Self {
+        Formatter {
+            unit_system: administrative.unit,
+            wind_unit: administrative.windunit,
+            pressure_unit: administrative.pressureunit.unwrap_or_default(),
+        }
+    }
+
+    /// Formats a temperature given in °C.
+    pub fn temperature(&self, celsius: f64) -> String {
+        match self.unit_system {
+            UnitSystem::Metric | UnitSystem::Other(_) => format!("{:.1} °C", celsius),
+            UnitSystem::Imperial => format!("{:.1} °F", celsius * 9.0 / 5.0 + 32.0),
+        }
+    }
+
+    /// Formats an amount of rain given in mm.
+    pub fn rain(&self, mm: f64) -> String {
+        match self.unit_system {
+            UnitSystem::Metric | UnitSystem::Other(_) => format!("{:.1} mm", mm),
+            UnitSystem::Imperial => format!("{:.2} in", mm / 25.4),
+        }
+    }
+
+    pub fn wind(&self, speed: WindSpeed) -> String {
+        match self.wind_unit {
+            WindUnit::Beaufort => format!("{} {}", speed.beaufort(), self.wind_unit),
+            unit => format!("{:.0} {}", speed.in_unit(unit), unit),
+        }
+    }
+
+    pub fn pressure(&self, pressure: Pressure) -> String {
+        pressure.format(self.pressure_unit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{comfort::FeelLikeAlgorithm, test_util::homes_data};
+
+    mod formatter {
+        use super::*;
+
+        #[test]
+        fn imperial() {
+            let user = User {
+                unit_system: UnitSystem::Imperial,
+                unit_wind: WindUnit::Mph,
+                unit_pressure: PressureUnit::InHg,
+                ..homes_data::user("user", "user@example.com")
+            };
+            let formatter = Formatter::for_user(&user);
+
+            assert_eq!(formatter.temperature(20.0), "68.0 °F");
+            assert_eq!(formatter.rain(25.4), "1.00 in");
+            assert_eq!(formatter.wind(WindSpeed::from_kph(16.09344)), "10 mph");
+            assert_eq!(formatter.pressure(Pressure::from_mbar(1013.25)), "29.92 inHg");
+        }
+
+        #[test]
+        fn preferences_round_trip() {
+            let units: (UnitSystem, WindUnit, PressureUnit) = serde_json::from_str("[1, 3, 9]").unwrap();
+
+            assert_eq!(
+                units,
+                (UnitSystem::Imperial, WindUnit::Beaufort, PressureUnit::Other(9))
+            );
+            assert_eq!(serde_json::to_string(&units).unwrap(), "[1,3,9]");
+
+            let unknown: (UnitSystem, WindUnit, FeelLikeAlgorithm) = serde_json::from_str("[7, 8, 9]").unwrap();
+            assert_eq!(serde_json::to_string(&unknown).unwrap(), "[7,8,9]");
+            assert_eq!(
+                Formatter {
+                    pressure_unit: PressureUnit::Other(9),
+                    ..Formatter::default()
+                }
+                .pressure(Pressure::from_mbar(1013.25)),
+                "1013.2 mbar"
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::{get_homes_data::Home, get_station_data::Device};
+use serde::{Deserialize, Serialize};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// A point on earth in degrees, (de)serialized as the `[longitude, latitude]` pair the API uses.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "[f64; 2]", into = "[f64; 2]")]
+#[non_exhaustive]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Coordinates { latitude, longitude }
+    }
+
+    /// Great-circle distance in km, using the haversine formula.
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        let d_lat = (other.latitude - self.latitude).to_radians();
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2)
+            + self.latitude.to_radians().cos() * other.latitude.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+impl From<[f64; 2]> for Coordinates {
+    fn from([longitude, latitude]: [f64; 2]) -> Self {
+        Coordinates { latitude, longitude }
+    }
+}
+
+impl From<Coordinates> for [f64; 2] {
+    fn from(c: Coordinates) -> Self {
+        [c.longitude, c.latitude]
+    }
+}
+
+impl Device {
+    pub fn distance_km(&self, reference: &Coordinates) -> f64 {
+        self.place.location.distance_km(reference)
+    }
+}
+
+impl Home {
+    /// `None` if the home's location was not set up in the app.
+    pub fn distance_km(&self, reference: &Coordinates) -> Option<f64> {
+        self.coordinates.map(|x| x.distance_km(reference))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod coordinates {
+        use super::*;
+
+        #[test]
+        fn distance_km() {
+            let paris = Coordinates::new(48.8566, 2.3522);
+            let london: Coordinates = serde_json::from_str("[-0.1276, 51.5072]").unwrap();
+
+            assert!((paris.distance_km(&london) - 343.5).abs() < 1.0);
+        }
+    }
+}
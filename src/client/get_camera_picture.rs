@@ -0,0 +1,102 @@
+use crate::{client::NetatmoClient, errors::Result};
+use bytes::Bytes;
+use std::collections::{HashMap, VecDeque};
+
+/// Bounded least-recently-used cache of camera pictures keyed by image id.
+#[derive(Debug)]
+pub struct PictureCache {
+    capacity: usize,
+    pictures: HashMap<String, Bytes>,
+    recently_used: VecDeque<String>,
+}
+
+impl PictureCache {
+    pub fn new(capacity: usize) -> Self {
+        PictureCache {
+            capacity,
+            pictures: HashMap::new(),
+            recently_used: VecDeque::new(),
+        }
+    }
+
+    pub fn get(&mut self, image_id: &str) -> Option<Bytes> {
+        let picture = self.pictures.get(image_id)?.clone();
+        self.touch(image_id);
+        Some(picture)
+    }
+
+    pub fn insert(&mut self, image_id: &str, picture: Bytes) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.pictures.insert(image_id.to_string(), picture).is_some() {
+            self.touch(image_id);
+            return;
+        }
+
+        self.recently_used.push_back(image_id.to_string());
+        if self.recently_used.len() > self.capacity {
+            if let Some(evicted) = self.recently_used.pop_front() {
+                self.pictures.remove(&evicted);
+            }
+        }
+    }
+
+    fn touch(&mut self, image_id: &str) {
+        if let Some(pos) = self.recently_used.iter().position(|x| x == image_id) {
+            if let Some(id) = self.recently_used.remove(pos) {
+                self.recently_used.push_back(id);
+            }
+        }
+    }
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/security#getcamerapicture
+pub async fn get_camera_picture(client: &NetatmoClient, image_id: &str, key: &str) -> Result<Bytes> {
+    if let Some(cache) = &client.picture_cache {
+        if let Some(picture) = cache.lock().unwrap().get(image_id) {
+            return Ok(picture);
+        }
+    }
+
+    let mut params: HashMap<&str, String> = HashMap::default();
+    params.insert("image_id", image_id.to_string());
+    params.insert("key", key.to_string());
+
+    let picture = client
+        .call_binary(
+            "get_camera_picture",
+            "https://api.netatmo.com/api/getcamerapicture",
+            &params,
+        )
+        .await?;
+
+    if let Some(cache) = &client.picture_cache {
+        cache.lock().unwrap().insert(image_id, picture.clone());
+    }
+
+    Ok(picture)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod picture_cache {
+        use super::*;
+
+        #[test]
+        fn evicts_least_recently_used() {
+            let mut cache = PictureCache::new(2);
+            cache.insert("a", Bytes::from_static(b"a"));
+            cache.insert("b", Bytes::from_static(b"b"));
+            assert!(cache.get("a").is_some());
+
+            cache.insert("c", Bytes::from_static(b"c"));
+
+            assert!(cache.get("a").is_some());
+            assert!(cache.get("b").is_none());
+            assert!(cache.get("c").is_some());
+        }
+    }
+}
@@ -0,0 +1,707 @@
+use super::{
+    get_homes_data::GatewayType,
+    get_next_events::{get_next_events, GetNextEventsParameters},
+    ids::{HomeId, ModuleId, PersonId},
+    mac_address::MacAddress,
+    retry_on_rate_limit,
+    timestamp::{epoch, Epoch, Timestamp},
+};
+use crate::{client::NetatmoClient, errors::Result};
+use futures::stream::{self, Stream};
+use log::debug;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::Infallible,
+    fmt,
+    future::Future,
+    str::FromStr,
+};
+
+const EVENTS_PAGE_SIZE: usize = 30;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Events {
+    pub status: String,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+    pub body: EventsBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct EventsBody {
+    pub home: EventsHome,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct EventsHome {
+    pub id: HomeId,
+    #[serde(default, deserialize_with = "de_events")]
+    pub events: Vec<Event>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Event {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_field: SecurityEventType,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time: Epoch,
+    pub module_id: ModuleId,
+    pub message: Option<String>,
+    // Welcome
+    pub person_id: Option<PersonId>,
+    pub is_arrival: Option<bool>,
+    // Welcome and Presence
+    pub video_id: Option<String>,
+    pub video_status: Option<VideoStatus>,
+    pub snapshot: Option<Snapshot>,
+    pub vignette: Option<Snapshot>,
+    // Presence
+    pub subevents: Option<Vec<SubEvent>>,
+    // Smoke detector
+    pub sub_type: Option<i64>,
+    /// The event as sent by the API, kept for event types this crate doesn't know or fails to parse
+    #[serde(skip)]
+    pub raw: Option<Value>,
+    /// Fields of the event this crate doesn't model yet
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl Event {
+    fn from_value(value: Value) -> Self {
+        match Event::deserialize(&value) {
+            Ok(event) if matches!(event.type_field, SecurityEventType::Other(_)) => Event {
+                raw: Some(value),
+                ..event
+            },
+            Ok(event) => event,
+            Err(e) => {
+                debug!("Failed to parse event, keeping it raw: {}", e);
+                let str_field = |name: &str| value.get(name).and_then(Value::as_str).unwrap_or_default().to_string();
+                Event {
+                    id: str_field("id"),
+                    type_field: SecurityEventType::from(str_field("type")),
+                    time: value
+                        .get("time")
+                        .and_then(Value::as_i64)
+                        .and_then(|x| epoch::from_seconds::<serde_json::Error>(x).ok())
+                        .unwrap_or_default(),
+                    module_id: str_field("module_id").into(),
+                    message: None,
+                    person_id: None,
+                    is_arrival: None,
+                    video_id: None,
+                    video_status: None,
+                    snapshot: None,
+                    vignette: None,
+                    subevents: None,
+                    sub_type: None,
+                    raw: Some(value),
+                    extra: HashMap::new(),
+                }
+            }
+        }
+    }
+}
+
+/// Parses a list of events without failing on single events the crate doesn't understand.
+pub(crate) fn de_events<'de, D>(deserializer: D) -> ::std::result::Result<Vec<Event>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let values = Vec::<Value>::deserialize(deserializer)?;
+    Ok(values.into_iter().map(Event::from_value).collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SubEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_field: SecurityEventType,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time: Epoch,
+    pub verified: Option<bool>,
+    pub offset: Option<i64>,
+    pub message: Option<String>,
+    pub snapshot: Option<Snapshot>,
+    pub vignette: Option<Snapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum SecurityEventType {
+    Person,
+    PersonAway,
+    Movement,
+    Human,
+    Animal,
+    Vehicle,
+    Outdoor,
+    DailySummary,
+    TagOpen,
+    TagBigMove,
+    TagSmallMove,
+    TagUninstalled,
+    Smoke,
+    Hush,
+    Tampered,
+    DetectionChamberStatus,
+    BatteryStatus,
+    SoundTest,
+    WifiStatus,
+    CoDetected,
+    Siren,
+    IncomingCall,
+    AcceptedCall,
+    MissedCall,
+    Connection,
+    Disconnection,
+    On,
+    Off,
+    ButtonPress,
+    Other(String),
+}
+
+impl Default for SecurityEventType {
+    fn default() -> Self {
+        SecurityEventType::Other(String::new())
+    }
+}
+
+impl fmt::Display for SecurityEventType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            SecurityEventType::Person => "person",
+            SecurityEventType::PersonAway => "person_away",
+            SecurityEventType::Movement => "movement",
+            SecurityEventType::Human => "human",
+            SecurityEventType::Animal => "animal",
+            SecurityEventType::Vehicle => "vehicle",
+            SecurityEventType::Outdoor => "outdoor",
+            SecurityEventType::DailySummary => "daily_summary",
+            SecurityEventType::TagOpen => "tag_open",
+            SecurityEventType::TagBigMove => "tag_big_move",
+            SecurityEventType::TagSmallMove => "tag_small_move",
+            SecurityEventType::TagUninstalled => "tag_uninstalled",
+            SecurityEventType::Smoke => "smoke",
+            SecurityEventType::Hush => "hush",
+            SecurityEventType::Tampered => "tampered",
+            SecurityEventType::DetectionChamberStatus => "detection_chamber_status",
+            SecurityEventType::BatteryStatus => "battery_status",
+            SecurityEventType::SoundTest => "sound_test",
+            SecurityEventType::WifiStatus => "wifi_status",
+            SecurityEventType::CoDetected => "co_detected",
+            SecurityEventType::Siren => "siren_sounding",
+            SecurityEventType::IncomingCall => "incoming_call",
+            SecurityEventType::AcceptedCall => "accepted_call",
+            SecurityEventType::MissedCall => "missed_call",
+            SecurityEventType::Connection => "connection",
+            SecurityEventType::Disconnection => "disconnection",
+            SecurityEventType::On => "on",
+            SecurityEventType::Off => "off",
+            SecurityEventType::ButtonPress => "button_press",
+            SecurityEventType::Other(s) => s,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl From<String> for SecurityEventType {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "person" => SecurityEventType::Person,
+            "person_away" => SecurityEventType::PersonAway,
+            "movement" => SecurityEventType::Movement,
+            "human" => SecurityEventType::Human,
+            "animal" => SecurityEventType::Animal,
+            "vehicle" => SecurityEventType::Vehicle,
+            "outdoor" => SecurityEventType::Outdoor,
+            "daily_summary" => SecurityEventType::DailySummary,
+            "tag_open" => SecurityEventType::TagOpen,
+            "tag_big_move" => SecurityEventType::TagBigMove,
+            "tag_small_move" => SecurityEventType::TagSmallMove,
+            "tag_uninstalled" => SecurityEventType::TagUninstalled,
+            "smoke" => SecurityEventType::Smoke,
+            "hush" => SecurityEventType::Hush,
+            "tampered" => SecurityEventType::Tampered,
+            "detection_chamber_status" => SecurityEventType::DetectionChamberStatus,
+            "battery_status" => SecurityEventType::BatteryStatus,
+            "sound_test" => SecurityEventType::SoundTest,
+            "wifi_status" => SecurityEventType::WifiStatus,
+            "co_detected" => SecurityEventType::CoDetected,
+            "siren_sounding" => SecurityEventType::Siren,
+            "incoming_call" => SecurityEventType::IncomingCall,
+            "accepted_call" => SecurityEventType::AcceptedCall,
+            "missed_call" => SecurityEventType::MissedCall,
+            "connection" => SecurityEventType::Connection,
+            "disconnection" => SecurityEventType::Disconnection,
+            "on" => SecurityEventType::On,
+            "off" => SecurityEventType::Off,
+            "button_press" => SecurityEventType::ButtonPress,
+            _ => SecurityEventType::Other(s),
+        }
+    }
+}
+
+impl From<SecurityEventType> for String {
+    fn from(t: SecurityEventType) -> Self {
+        t.to_string()
+    }
+}
+
+impl FromStr for SecurityEventType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(SecurityEventType::from(s.to_string()))
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum VideoStatus {
+    Recording,
+    Available,
+    Deleted,
+    #[default]
+    #[serde(other)]
+    Error,
+}
+
+impl fmt::Display for VideoStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            VideoStatus::Recording => "recording",
+            VideoStatus::Available => "available",
+            VideoStatus::Deleted => "deleted",
+            VideoStatus::Error => "error",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for VideoStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "recording" => VideoStatus::Recording,
+            "available" => VideoStatus::Available,
+            "deleted" => VideoStatus::Deleted,
+            _ => VideoStatus::Error,
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Snapshot {
+    pub id: Option<String>,
+    pub key: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GetEventsParameters {
+    home_id: HomeId,
+    device_types: Option<Vec<GatewayType>>,
+    device_id: Option<MacAddress>,
+    module_id: Option<MacAddress>,
+    person_id: Option<PersonId>,
+    size: Option<usize>,
+}
+
+impl GetEventsParameters {
+    pub fn new(home_id: &HomeId) -> Self {
+        GetEventsParameters {
+            home_id: home_id.clone(),
+            device_types: None,
+            device_id: None,
+            module_id: None,
+            person_id: None,
+            size: None,
+        }
+    }
+
+    pub fn home_id(&self) -> &HomeId {
+        &self.home_id
+    }
+
+    pub fn device_types(self, device_types: &[GatewayType]) -> Self {
+        GetEventsParameters {
+            device_types: Some(device_types.to_vec()),
+            ..self
+        }
+    }
+
+    pub fn device_id(self, device_id: &MacAddress) -> Self {
+        GetEventsParameters {
+            device_id: Some(device_id.clone()),
+            ..self
+        }
+    }
+
+    pub fn module_id(self, module_id: &MacAddress) -> Self {
+        GetEventsParameters {
+            module_id: Some(module_id.clone()),
+            ..self
+        }
+    }
+
+    pub fn person_id(self, person_id: &PersonId) -> Self {
+        GetEventsParameters {
+            person_id: Some(person_id.clone()),
+            ..self
+        }
+    }
+
+    pub fn size(self, size: usize) -> Self {
+        GetEventsParameters {
+            size: Some(size),
+            ..self
+        }
+    }
+}
+
+#[allow(clippy::implicit_hasher)]
+impl From<&GetEventsParameters> for HashMap<&'static str, String> {
+    fn from(p: &GetEventsParameters) -> HashMap<&'static str, String> {
+        let mut map = HashMap::default();
+        map.insert("home_id", p.home_id.to_string());
+        if let Some(device_types) = &p.device_types {
+            let device_types = device_types
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .as_slice()
+                .join(",");
+            map.insert("device_types", device_types);
+        }
+        if let Some(device_id) = &p.device_id {
+            map.insert("device_id", device_id.to_string());
+        }
+        if let Some(module_id) = &p.module_id {
+            map.insert("module_id", module_id.to_string());
+        }
+        if let Some(person_id) = &p.person_id {
+            map.insert("person_id", person_id.to_string());
+        }
+        if let Some(size) = p.size {
+            map.insert("size", size.to_string());
+        }
+
+        map
+    }
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/security#getevents
+pub async fn get_events(client: &NetatmoClient, parameters: &GetEventsParameters) -> Result<Events> {
+    let params: HashMap<&str, String> = parameters.into();
+    client
+        .call("get_events", "https://api.netatmo.com/api/getevents", &params)
+        .await
+}
+
+struct EventsPagination<F> {
+    fetch: F,
+    since: i64,
+    page: VecDeque<Event>,
+    last_event_id: Option<String>,
+    exhausted: bool,
+}
+
+/// Streams the events of a home from now back to `since`, fetching further pages on demand.
+///
+/// Calls that hit the rate limit are retried with a backoff; any other error ends the stream after being yielded.
+pub fn events_since<'a>(
+    client: &'a NetatmoClient,
+    home_id: &HomeId,
+    since: i64,
+) -> impl Stream<Item = Result<Event>> + 'a {
+    let home_id = home_id.clone();
+    paginate(since, move |last_event_id| {
+        let home_id = home_id.clone();
+        async move {
+            match last_event_id {
+                None => {
+                    let parameters = GetEventsParameters::new(&home_id).size(EVENTS_PAGE_SIZE);
+                    retry_on_rate_limit(|| get_events(client, &parameters))
+                        .await
+                        .map(|x| x.body.home.events)
+                }
+                Some(event_id) => {
+                    let parameters = GetNextEventsParameters::new(&home_id, &event_id).size(EVENTS_PAGE_SIZE);
+                    retry_on_rate_limit(|| get_next_events(client, &parameters))
+                        .await
+                        .map(|x| x.body.events_list)
+                }
+            }
+        }
+    })
+}
+
+/// Streams the events of the pages `fetch` returns, given the id of the last event streamed so far, until an event is
+/// older than `since`, a page is empty or fetching fails.
+fn paginate<F, Fut>(since: i64, fetch: F) -> impl Stream<Item = Result<Event>>
+where
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Vec<Event>>>,
+{
+    let pagination = EventsPagination {
+        fetch,
+        since,
+        page: VecDeque::new(),
+        last_event_id: None,
+        exhausted: false,
+    };
+
+    stream::unfold(pagination, |mut state| async move {
+        loop {
+            if let Some(event) = state.page.pop_front() {
+                if event.time.epoch_seconds() < state.since {
+                    return None;
+                }
+                state.last_event_id = Some(event.id.clone());
+                return Some((Ok(event), state));
+            }
+            if state.exhausted {
+                return None;
+            }
+
+            match (state.fetch)(state.last_event_id.clone()).await {
+                Ok(events) if events.is_empty() => return None,
+                Ok(events) => state.page = events.into(),
+                Err(e) => {
+                    state.exhausted = true;
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::assert_round_trip;
+
+    mod get_events {
+        use super::*;
+
+        #[test]
+        fn parse_response() {
+            let json = r#"{
+                "body": {
+                  "home": {
+                    "id": "...",
+                    "events": [
+                      {
+                        "id": "...",
+                        "type": "person",
+                        "time": 1689865621,
+                        "module_id": "70:ee:50:00:00:01",
+                        "message": "<b>John</b> seen",
+                        "person_id": "...",
+                        "is_arrival": false,
+                        "video_id": "...",
+                        "video_status": "available",
+                        "snapshot": {
+                          "id": "...",
+                          "version": 1,
+                          "key": "...",
+                          "url": "https://netatmocameraimage.blob.core.windows.net/production/..."
+                        }
+                      },
+                      {
+                        "id": "...",
+                        "type": "outdoor",
+                        "time": 1689865000,
+                        "module_id": "70:ee:50:00:00:02",
+                        "video_id": "...",
+                        "video_status": "available",
+                        "subevents": [
+                          {
+                            "id": "...",
+                            "type": "human",
+                            "time": 1689865000,
+                            "verified": true,
+                            "offset": 0,
+                            "message": "Person seen",
+                            "snapshot": {
+                              "url": "https://netatmocameraimage.blob.core.windows.net/production/..."
+                            },
+                            "vignette": {
+                              "url": "https://netatmocameraimage.blob.core.windows.net/production/..."
+                            }
+                          }
+                        ]
+                      },
+                      {
+                        "id": "...",
+                        "type": "hush",
+                        "time": 1689864000,
+                        "module_id": "70:ee:50:00:00:03",
+                        "message": "Alarm silenced",
+                        "sub_type": 0
+                      }
+                    ]
+                  }
+                },
+                "status": "ok",
+                "time_server": 1689865700
+              }"#;
+
+            let events: std::result::Result<Events, _> = serde_json::from_str(json);
+
+            assert!(&events.is_ok());
+        }
+
+        #[test]
+        fn parse_unrecognized_events() {
+            let json = r#"{
+                "body": {
+                  "home": {
+                    "id": "...",
+                    "events": [
+                      {
+                        "id": "1",
+                        "type": "brand_new_kind",
+                        "time": 1689865621,
+                        "module_id": "70:ee:50:00:00:01",
+                        "new_field": {
+                          "nested": true
+                        }
+                      },
+                      {
+                        "id": "2",
+                        "type": "movement",
+                        "time": "not a timestamp",
+                        "module_id": "70:ee:50:00:00:01"
+                      },
+                      {
+                        "id": "3",
+                        "type": "movement",
+                        "time": 1689865000,
+                        "module_id": "70:ee:50:00:00:01"
+                      }
+                    ]
+                  }
+                },
+                "status": "ok",
+                "time_server": 1689865700
+              }"#;
+
+            let events: Events = serde_json::from_str(json).unwrap();
+            let events = events.body.home.events;
+
+            assert_eq!(events.len(), 3);
+            assert_eq!(
+                events[0].type_field,
+                SecurityEventType::Other("brand_new_kind".to_string())
+            );
+            assert!(events[0].raw.as_ref().unwrap().get("new_field").is_some());
+            assert_eq!(events[1].type_field, SecurityEventType::Movement);
+            assert!(events[1].raw.is_some());
+            assert!(events[2].raw.is_none());
+        }
+
+        #[test]
+        fn event_type_round_trip() {
+            assert_round_trip!(
+                SecurityEventType::TagOpen,
+                SecurityEventType::Other("new_kind".to_string()),
+            );
+
+            assert_round_trip!(
+                VideoStatus::Recording,
+                VideoStatus::Available,
+                VideoStatus::Deleted,
+                VideoStatus::Error,
+            );
+        }
+    }
+
+    mod events_since {
+        use super::*;
+        use crate::{client::test_util::events, errors::NetatmoError};
+        use futures::StreamExt;
+        use std::cell::RefCell;
+
+        /// The ids of the events streamed, `None` for an error, and the `last_event_id` of each page fetched.
+        async fn stream(since: i64, pages: Vec<Result<Vec<Event>>>) -> (Vec<Option<String>>, Vec<Option<String>>) {
+            let pages = RefCell::new(pages.into_iter());
+            let fetched = RefCell::new(vec![]);
+
+            let streamed = paginate(since, |last_event_id| {
+                fetched.borrow_mut().push(last_event_id);
+                let page = pages.borrow_mut().next().expect("fetched more pages than there are");
+                async move { page }
+            })
+            .map(|x| x.ok().map(|x| x.id))
+            .collect()
+            .await;
+
+            (streamed, fetched.into_inner())
+        }
+
+        fn page(events: &[(&str, i64)]) -> Result<Vec<Event>> {
+            Ok(events
+                .iter()
+                .map(|(id, time)| events::event(id, SecurityEventType::Movement, *time))
+                .collect())
+        }
+
+        #[tokio::test]
+        async fn stops_at_since() {
+            let (streamed, fetched) = stream(
+                100,
+                vec![page(&[("1", 400), ("2", 300)]), page(&[("3", 200), ("4", 50)])],
+            )
+            .await;
+
+            assert_eq!(
+                streamed,
+                [Some("1".to_string()), Some("2".to_string()), Some("3".to_string())]
+            );
+            assert_eq!(fetched, [None, Some("2".to_string())]);
+        }
+
+        #[tokio::test]
+        async fn stops_at_empty_page() {
+            let (streamed, fetched) = stream(100, vec![page(&[("1", 400)]), page(&[])]).await;
+
+            assert_eq!(streamed, [Some("1".to_string())]);
+            assert_eq!(fetched, [None, Some("1".to_string())]);
+        }
+
+        #[tokio::test]
+        async fn ends_after_error() {
+            let (streamed, fetched) =
+                stream(100, vec![page(&[("1", 400)]), Err(NetatmoError::FailedToSendRequest)]).await;
+
+            assert_eq!(streamed, [Some("1".to_string()), None]);
+            assert_eq!(fetched, [None, Some("1".to_string())]);
+        }
+    }
+}
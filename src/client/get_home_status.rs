@@ -1,63 +1,273 @@
-use super::get_homes_data::GatewayType;
+use super::{
+    get_homes_data::GatewayType,
+    ids::{HomeId, ModuleId, PersonId, RoomId},
+    module_status::ModuleStatus,
+    timestamp::{epoch, Epoch},
+};
 use crate::{
     client::NetatmoClient,
     errors::{NetatmoError, Result},
 };
 use serde::{Deserialize, Deserializer, Serialize};
-use serde_repr::*;
-use std::{collections::HashMap, str::FromStr};
+use serde_json::Value;
+use std::{collections::HashMap, convert::Infallible, fmt, str::FromStr};
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct HomeStatus {
     pub status: String,
-    pub time_server: i64,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
     pub body: HomeStatusBody,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct HomeStatusBody {
     pub home: Home,
     pub errors: Option<Vec<HomeStatusError>>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Home {
-    pub id: String,
-    pub modules: Option<Vec<Module>>,
+    pub id: HomeId,
+    pub modules: Option<Vec<ModuleStatus>>,
     pub rooms: Option<Vec<Room>>,
+    pub persons: Option<Vec<PersonStatus>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct PersonStatus {
+    pub id: PersonId,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub last_seen: Option<Epoch>,
+    pub out_of_sight: Option<bool>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Module {
-    pub id: String,
-    #[serde(rename = "type")]
-    pub type_field: String,
-    pub firmware_revision: i64,
-    pub rf_strength: Option<i64>,
-    pub wifi_strength: Option<i64>,
-    pub reachable: Option<bool>,
-    pub battery_level: Option<i64>,
-    pub boiler_valve_comfort_boost: Option<bool>,
-    pub boiler_status: Option<bool>,
-    pub anticipating: Option<bool>,
-    pub bridge: Option<String>,
-    pub battery_state: Option<String>,
-    pub status_active: Option<bool>,
-    pub status_tampered: Option<bool>,
-    pub test_mode: Option<bool>,
-    pub hush_mode: Option<bool>,
-    pub smoke_detected: Option<bool>,
-    pub detection_chamber_status: Option<String>,
-    pub battery_alarm_state: Option<String>,
-    pub battery_percent: Option<i64>,
-    pub wifi_status: Option<i64>,
-    pub last_smoke_detected_start_time: Option<i64>,
-    pub last_smoke_detected_end_time: Option<i64>,
-    pub last_seen: Option<i64>,
-    pub last_wifi_connection: Option<i64>,
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum GatewayLinkState {
+    Connected,
+    Disconnected,
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+impl fmt::Display for GatewayLinkState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            GatewayLinkState::Connected => "connected",
+            GatewayLinkState::Disconnected => "disconnected",
+            GatewayLinkState::Unknown => "unknown",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for GatewayLinkState {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "connected" => GatewayLinkState::Connected,
+            "disconnected" => GatewayLinkState::Disconnected,
+            _ => GatewayLinkState::Unknown,
+        })
+    }
 }
 
+/// Whether a gateway is accepting new modules, while an installer adds them.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum PairingState {
+    Idle,
+    InProgress,
+    Done,
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+impl fmt::Display for PairingState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            PairingState::Idle => "idle",
+            PairingState::InProgress => "in_progress",
+            PairingState::Done => "done",
+            PairingState::Unknown => "unknown",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for PairingState {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "idle" => PairingState::Idle,
+            "in_progress" => PairingState::InProgress,
+            "done" => PairingState::Done,
+            _ => PairingState::Unknown,
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum TagStatus {
+    Open,
+    Closed,
+    NoNews,
+    Calibrating,
+    CalibrationFailed,
+    Maintenance,
+    WeakSignal,
+    #[default]
+    #[serde(other)]
+    Undefined,
+}
+
+impl fmt::Display for TagStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            TagStatus::Open => "open",
+            TagStatus::Closed => "closed",
+            TagStatus::NoNews => "no_news",
+            TagStatus::Calibrating => "calibrating",
+            TagStatus::CalibrationFailed => "calibration_failed",
+            TagStatus::Maintenance => "maintenance",
+            TagStatus::WeakSignal => "weak_signal",
+            TagStatus::Undefined => "undefined",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for TagStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "open" => TagStatus::Open,
+            "closed" => TagStatus::Closed,
+            "no_news" => TagStatus::NoNews,
+            "calibrating" => TagStatus::Calibrating,
+            "calibration_failed" => TagStatus::CalibrationFailed,
+            "maintenance" => TagStatus::Maintenance,
+            "weak_signal" => TagStatus::WeakSignal,
+            _ => TagStatus::Undefined,
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "u8", into = "u8")]
+#[non_exhaustive]
+pub enum SdCardStatus {
+    #[default]
+    Missing,
+    Inserted,
+    Formatted,
+    Working,
+    Defective,
+    IncompatibleSpeed,
+    InsufficientSpace,
+    Other(u8),
+}
+
+impl From<u8> for SdCardStatus {
+    fn from(n: u8) -> Self {
+        match n {
+            1 => SdCardStatus::Missing,
+            2 => SdCardStatus::Inserted,
+            3 => SdCardStatus::Formatted,
+            4 => SdCardStatus::Working,
+            5 => SdCardStatus::Defective,
+            6 => SdCardStatus::IncompatibleSpeed,
+            7 => SdCardStatus::InsufficientSpace,
+            _ => SdCardStatus::Other(n),
+        }
+    }
+}
+
+impl From<SdCardStatus> for u8 {
+    fn from(s: SdCardStatus) -> Self {
+        match s {
+            SdCardStatus::Missing => 1,
+            SdCardStatus::Inserted => 2,
+            SdCardStatus::Formatted => 3,
+            SdCardStatus::Working => 4,
+            SdCardStatus::Defective => 5,
+            SdCardStatus::IncompatibleSpeed => 6,
+            SdCardStatus::InsufficientSpace => 7,
+            SdCardStatus::Other(n) => n,
+        }
+    }
+}
+
+impl SdCardStatus {
+    pub fn needs_attention(&self) -> bool {
+        !matches!(
+            self,
+            SdCardStatus::Inserted | SdCardStatus::Formatted | SdCardStatus::Working
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "u8", into = "u8")]
+#[non_exhaustive]
+pub enum PowerAdapterStatus {
+    #[default]
+    Incorrect,
+    Correct,
+    Other(u8),
+}
+
+impl From<u8> for PowerAdapterStatus {
+    fn from(n: u8) -> Self {
+        match n {
+            1 => PowerAdapterStatus::Incorrect,
+            2 => PowerAdapterStatus::Correct,
+            _ => PowerAdapterStatus::Other(n),
+        }
+    }
+}
+
+impl From<PowerAdapterStatus> for u8 {
+    fn from(s: PowerAdapterStatus) -> Self {
+        match s {
+            PowerAdapterStatus::Incorrect => 1,
+            PowerAdapterStatus::Correct => 2,
+            PowerAdapterStatus::Other(n) => n,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
 pub enum ModuleBatteryState {
     #[default]
     VeryLow,
@@ -65,6 +275,33 @@ pub enum ModuleBatteryState {
     Medium,
     High,
     Full,
+    Other(String),
+}
+
+impl fmt::Display for ModuleBatteryState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            ModuleBatteryState::VeryLow => "very_low",
+            ModuleBatteryState::Low => "low",
+            ModuleBatteryState::Medium => "medium",
+            ModuleBatteryState::High => "high",
+            ModuleBatteryState::Full => "full",
+            ModuleBatteryState::Other(s) => s,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl From<String> for ModuleBatteryState {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(ModuleBatteryState::Other(s))
+    }
+}
+
+impl From<ModuleBatteryState> for String {
+    fn from(s: ModuleBatteryState) -> Self {
+        s.to_string()
+    }
 }
 
 impl FromStr for ModuleBatteryState {
@@ -82,23 +319,41 @@ impl FromStr for ModuleBatteryState {
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Rooms without heating, e.g. in homes with only cameras, and rooms whose modules are unreachable omit most fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Room {
-    pub id: String,
-    pub reachable: bool,
-    pub heating_power_request: i64,
-    pub therm_measured_temperature: f64,
-    pub therm_setpoint_temperature: f64,
-    pub therm_setpoint_mode: ThermSetpointMode,
-    #[serde(deserialize_with = "de_setpoint_timestamp")]
-    pub therm_setpoint_start_time: i64,
-    #[serde(deserialize_with = "de_setpoint_timestamp")]
-    pub therm_setpoint_end_time: i64,
-    pub anticipating: bool,
-    pub open_window: bool,
+    pub id: RoomId,
+    pub reachable: Option<bool>,
+    pub heating_power_request: Option<i64>,
+    pub therm_measured_temperature: Option<f64>,
+    pub therm_setpoint_temperature: Option<f64>,
+    pub therm_setpoint_mode: Option<ThermSetpointMode>,
+    #[serde(
+        default,
+        serialize_with = "epoch::option::serialize",
+        deserialize_with = "de_setpoint_timestamp"
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub therm_setpoint_start_time: Option<Epoch>,
+    #[serde(
+        default,
+        serialize_with = "epoch::option::serialize",
+        deserialize_with = "de_setpoint_timestamp"
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub therm_setpoint_end_time: Option<Epoch>,
+    pub anticipating: Option<bool>,
+    pub open_window: Option<bool>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
 pub enum ThermSetpointMode {
     #[default]
     Manual,
@@ -107,6 +362,34 @@ pub enum ThermSetpointMode {
     Schedule,
     Away,
     Hg,
+    Other(String),
+}
+
+impl fmt::Display for ThermSetpointMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            ThermSetpointMode::Manual => "manual",
+            ThermSetpointMode::Max => "max",
+            ThermSetpointMode::Off => "off",
+            ThermSetpointMode::Schedule => "schedule",
+            ThermSetpointMode::Away => "away",
+            ThermSetpointMode::Hg => "hg",
+            ThermSetpointMode::Other(s) => s,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl From<String> for ThermSetpointMode {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(ThermSetpointMode::Other(s))
+    }
+}
+
+impl From<ThermSetpointMode> for String {
+    fn from(m: ThermSetpointMode) -> Self {
+        m.to_string()
+    }
 }
 
 impl FromStr for ThermSetpointMode {
@@ -129,7 +412,7 @@ impl FromStr for ThermSetpointMode {
     }
 }
 
-fn de_setpoint_timestamp<'de, D>(deserializer: D) -> ::std::result::Result<i64, D::Error>
+fn de_setpoint_timestamp<'de, D>(deserializer: D) -> ::std::result::Result<Option<Epoch>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -143,37 +426,72 @@ where
         String(String),
     }
 
-    let timestamp_value = SetpointTimestamp::deserialize(deserializer)?;
-    match timestamp_value {
-        SetpointTimestamp::Integer(i) => Ok(i),
-        SetpointTimestamp::String(s) => {
+    let timestamp_value = Option::<SetpointTimestamp>::deserialize(deserializer)?;
+    let seconds = match timestamp_value {
+        None => return Ok(None),
+        Some(SetpointTimestamp::Integer(i)) => i,
+        Some(SetpointTimestamp::String(s)) => {
             let s = s.split(", ").next().unwrap_or(&s);
-            i64::from_str(s).map_err(serde::de::Error::custom)
+            i64::from_str(s).map_err(serde::de::Error::custom)?
         }
-    }
+    };
+    epoch::from_seconds(seconds).map(Some)
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct HomeStatusError {
     pub code: HomeStatusErrorCode,
-    pub id: String,
+    pub id: ModuleId,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize_repr, Deserialize_repr)]
-#[repr(u8)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum HomeStatusErrorCode {
     #[default]
-    UnknownError = 1,
-    InternalError = 2,
-    ParserError = 3,
-    CommandUnknownNodeModuleError = 4,
-    CommandInvalidParams = 5,
-    Unreachable = 6,
+    UnknownError,
+    InternalError,
+    ParserError,
+    CommandUnknownNodeModuleError,
+    CommandInvalidParams,
+    Unreachable,
+    Other(u8),
+}
+
+impl From<u8> for HomeStatusErrorCode {
+    fn from(n: u8) -> Self {
+        match n {
+            1 => HomeStatusErrorCode::UnknownError,
+            2 => HomeStatusErrorCode::InternalError,
+            3 => HomeStatusErrorCode::ParserError,
+            4 => HomeStatusErrorCode::CommandUnknownNodeModuleError,
+            5 => HomeStatusErrorCode::CommandInvalidParams,
+            6 => HomeStatusErrorCode::Unreachable,
+            _ => HomeStatusErrorCode::Other(n),
+        }
+    }
+}
+
+impl From<HomeStatusErrorCode> for u8 {
+    fn from(c: HomeStatusErrorCode) -> Self {
+        match c {
+            HomeStatusErrorCode::UnknownError => 1,
+            HomeStatusErrorCode::InternalError => 2,
+            HomeStatusErrorCode::ParserError => 3,
+            HomeStatusErrorCode::CommandUnknownNodeModuleError => 4,
+            HomeStatusErrorCode::CommandInvalidParams => 5,
+            HomeStatusErrorCode::Unreachable => 6,
+            HomeStatusErrorCode::Other(n) => n,
+        }
+    }
 }
 
-#[derive(Default)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
 pub struct GetHomeStatusParameters {
-    home_id: Option<String>,
+    home_id: Option<HomeId>,
     device_types: Option<Vec<GatewayType>>,
 }
 
@@ -182,9 +500,9 @@ impl GetHomeStatusParameters {
         GetHomeStatusParameters::default()
     }
 
-    pub fn home_id(self, home_id: &str) -> Self {
+    pub fn home_id(self, home_id: &HomeId) -> Self {
         GetHomeStatusParameters {
-            home_id: Some(home_id.to_string()),
+            home_id: Some(home_id.clone()),
             ..self
         }
     }
@@ -198,11 +516,11 @@ impl GetHomeStatusParameters {
 }
 
 #[allow(clippy::implicit_hasher)]
-impl From<&GetHomeStatusParameters> for HashMap<String, String> {
-    fn from(p: &GetHomeStatusParameters) -> HashMap<String, String> {
+impl From<&GetHomeStatusParameters> for HashMap<&'static str, String> {
+    fn from(p: &GetHomeStatusParameters) -> HashMap<&'static str, String> {
         let mut map = HashMap::default();
         if let Some(home_id) = &p.home_id {
-            map.insert("home_id".to_string(), home_id.to_string());
+            map.insert("home_id", home_id.to_string());
         }
         if let Some(device_types) = &p.device_types {
             let device_types = device_types
@@ -211,7 +529,7 @@ impl From<&GetHomeStatusParameters> for HashMap<String, String> {
                 .collect::<Vec<_>>()
                 .as_slice()
                 .join(",");
-            map.insert("device_types".to_string(), device_types);
+            map.insert("device_types", device_types);
         }
 
         map
@@ -219,16 +537,16 @@ impl From<&GetHomeStatusParameters> for HashMap<String, String> {
 }
 
 pub async fn get_home_status(client: &NetatmoClient, parameters: &GetHomeStatusParameters) -> Result<HomeStatus> {
-    let params: HashMap<String, String> = parameters.into();
-    let mut params = params.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    let params: HashMap<&str, String> = parameters.into();
     client
-        .call("get_home_status", "https://api.netatmo.com/api/homestatus", &mut params)
+        .call("get_home_status", "https://api.netatmo.com/api/homestatus", &params)
         .await
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::client::test_util::{assert_round_trip, at};
 
     mod get_home_status {
         use super::*;
@@ -248,6 +566,68 @@ mod test {
                         "firmware_revision": 108,
                         "last_seen": 1622622024,
                         "wifi_strength": 35
+                      },
+                      {
+                        "id": "...",
+                        "type": "NLG",
+                        "firmware_revision": 211,
+                        "hardware_version": 3,
+                        "offload": false,
+                        "wifi_strength": 38,
+                        "wifi_state": "connected",
+                        "ethernet_state": "disconnected",
+                        "pairing": "idle"
+                      },
+                      {
+                        "id": "...",
+                        "type": "NLP",
+                        "firmware_revision": 62,
+                        "bridge": "...",
+                        "on": true,
+                        "power": 41
+                      },
+                      {
+                        "id": "...",
+                        "type": "NLV",
+                        "firmware_revision": 41,
+                        "bridge": "...",
+                        "current_position": 0,
+                        "target_position": 100
+                      },
+                      {
+                        "id": "...",
+                        "type": "NLF",
+                        "firmware_revision": 60,
+                        "bridge": "...",
+                        "on": true,
+                        "brightness": 80
+                      },
+                      {
+                        "id": "...",
+                        "type": "NLPC",
+                        "firmware_revision": 62,
+                        "bridge": "...",
+                        "power": 1450,
+                        "sum_energy_elec": 1298734
+                      },
+                      {
+                        "id": "...",
+                        "type": "NACamera",
+                        "firmware_revision": 301,
+                        "vpn_url": "https://prodvpn-eu-2.netatmo.net/restricted/10.255.0.1/abc",
+                        "is_local": true,
+                        "sd_status": 4,
+                        "alim_status": 2,
+                        "wifi_strength": 60
+                      },
+                      {
+                        "id": "...",
+                        "type": "DTG",
+                        "firmware_revision": 58,
+                        "bridge": "...",
+                        "rf_strength": 80,
+                        "battery_state": "high",
+                        "status": "closed"
                       }
                     ]
                   },
@@ -260,9 +640,173 @@ mod test {
                 }
               }"#;
 
-            let home_status: std::result::Result<HomeStatus, _> = serde_json::from_str(json);
+            let home_status: HomeStatus = serde_json::from_str(json).unwrap();
+            let modules = home_status.body.home.modules.unwrap();
+
+            assert_eq!(modules.len(), 8);
+            match &modules[1] {
+                ModuleStatus::Gateway(gateway) => {
+                    assert_eq!(gateway.wifi_state, Some(GatewayLinkState::Connected));
+                    assert_eq!(gateway.ethernet_state, Some(GatewayLinkState::Disconnected));
+                    assert_eq!(gateway.pairing, Some(PairingState::Idle));
+                    assert_eq!(gateway.offload, Some(false));
+                }
+                x => panic!("expected a gateway, got {:?}", x),
+            }
+            match &modules[2] {
+                ModuleStatus::Switch(plug) => {
+                    assert_eq!(plug.on, Some(true));
+                    assert_eq!(plug.power, Some(41));
+                }
+                x => panic!("expected a switch, got {:?}", x),
+            }
+            match &modules[3] {
+                ModuleStatus::Shutter(shutter) => {
+                    assert_eq!(shutter.current_position, Some(0));
+                    assert_eq!(shutter.target_position, Some(100));
+                }
+                x => panic!("expected a shutter, got {:?}", x),
+            }
+            match &modules[4] {
+                ModuleStatus::Dimmer(dimmer) => {
+                    assert_eq!(dimmer.on, Some(true));
+                    assert_eq!(dimmer.brightness, Some(80));
+                }
+                x => panic!("expected a dimmer, got {:?}", x),
+            }
+            match &modules[5] {
+                ModuleStatus::EnergyMeter(meter) => {
+                    assert_eq!(meter.power, Some(1450));
+                    assert_eq!(meter.sum_energy_elec, Some(1298734));
+                }
+                x => panic!("expected an energy meter, got {:?}", x),
+            }
+            assert!(modules.iter().all(|x| !matches!(x, ModuleStatus::Unknown(_))));
+        }
+
+        #[test]
+        fn parse_tag_status() {
+            let open: TagStatus = serde_json::from_str(r#""open""#).unwrap();
+            let unknown: TagStatus = serde_json::from_str(r#""something_new""#).unwrap();
+
+            assert_eq!(open, TagStatus::Open);
+            assert_eq!(unknown, TagStatus::Undefined);
+            assert_eq!("something_new".parse::<TagStatus>(), Ok(TagStatus::Undefined));
+        }
+
+        #[test]
+        fn enum_round_trip() {
+            assert_round_trip!(
+                GatewayLinkState::Connected,
+                GatewayLinkState::Disconnected,
+                GatewayLinkState::Unknown,
+                PairingState::Idle,
+                PairingState::InProgress,
+                PairingState::Done,
+                PairingState::Unknown,
+                TagStatus::Open,
+                TagStatus::Closed,
+                TagStatus::NoNews,
+                TagStatus::Calibrating,
+                TagStatus::CalibrationFailed,
+                TagStatus::Maintenance,
+                TagStatus::WeakSignal,
+                TagStatus::Undefined,
+                ModuleBatteryState::VeryLow,
+                ModuleBatteryState::Low,
+                ModuleBatteryState::Medium,
+                ModuleBatteryState::High,
+                ModuleBatteryState::Full,
+                ThermSetpointMode::Manual,
+                ThermSetpointMode::Max,
+                ThermSetpointMode::Off,
+                ThermSetpointMode::Schedule,
+                ThermSetpointMode::Away,
+                ThermSetpointMode::Hg,
+            );
+        }
+
+        #[test]
+        fn parse_unknown_values() {
+            let json = r#"{
+                "id": "2255",
+                "reachable": true,
+                "heating_power_request": 0,
+                "therm_measured_temperature": 19.8,
+                "therm_setpoint_temperature": 19.5,
+                "therm_setpoint_mode": "something_new",
+                "therm_setpoint_start_time": 1689865621,
+                "therm_setpoint_end_time": 0,
+                "anticipating": false,
+                "open_window": false
+            }"#;
+
+            let room: Room = serde_json::from_str(json).unwrap();
+            let error: HomeStatusError = serde_json::from_str(r#"{"code": 42, "id": "12:34:56:78:90:ab"}"#).unwrap();
+            let sd_status: SdCardStatus = serde_json::from_str("9").unwrap();
+
+            assert_eq!(
+                room.therm_setpoint_mode,
+                Some(ThermSetpointMode::Other("something_new".to_string()))
+            );
+            assert_eq!(error.code, HomeStatusErrorCode::Other(42));
+            assert_eq!(sd_status, SdCardStatus::Other(9));
+            assert_eq!(
+                serde_json::to_string(&room.therm_setpoint_mode).unwrap(),
+                r#""something_new""#
+            );
+        }
+
+        #[cfg(feature = "schemars")]
+        #[test]
+        fn json_schema() {
+            let schema = serde_json::to_value(schemars::schema_for!(HomeStatus)).unwrap();
+
+            assert!(schema["properties"]["body"].is_object());
+        }
+
+        #[test]
+        fn parse_camera_only_home() {
+            let json = r#"{
+                "status": "ok",
+                "time_server": 1689865621,
+                "body": {
+                  "home": {
+                    "id": "...",
+                    "modules": [
+                      {
+                        "id": "...",
+                        "type": "NOC",
+                        "firmware_revision": 3002000,
+                        "vpn_url": "https://prodvpn-eu-6.netatmo.net/restricted/10.255.0.2/def",
+                        "is_local": false,
+                        "sd_status": 4,
+                        "alim_status": 2,
+                        "wifi_strength": 52
+                      }
+                    ],
+                    "rooms": [
+                      {
+                        "id": "2255"
+                      },
+                      {
+                        "id": "3366",
+                        "reachable": false,
+                        "therm_setpoint_mode": "schedule, away",
+                        "therm_setpoint_start_time": "1622622024, 1622622024"
+                      }
+                    ]
+                  }
+                }
+              }"#;
+
+            let home_status: HomeStatus = serde_json::from_str(json).unwrap();
+            let rooms = home_status.body.home.rooms.unwrap();
 
-            assert!(&home_status.is_ok());
+            assert_eq!(rooms[0].therm_measured_temperature, None);
+            assert_eq!(rooms[0].therm_setpoint_start_time, None);
+            assert_eq!(rooms[1].therm_setpoint_mode, Some(ThermSetpointMode::Schedule));
+            assert_eq!(rooms[1].therm_setpoint_start_time, Some(at(1622622024)));
         }
     }
 }
@@ -0,0 +1,250 @@
+use super::{
+    get_station_data::{Place, User},
+    mac_address::MacAddress,
+    module_type::ModuleType,
+    timestamp::{epoch, Epoch},
+};
+use crate::{client::NetatmoClient, errors::Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct HomeCoachData {
+    pub body: HomeCoachDataBody,
+    pub status: String,
+    pub time_exec: f64,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct HomeCoachDataBody {
+    pub devices: Vec<HomeCoach>,
+    pub user: User,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct HomeCoach {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub name: Option<String>,
+    pub co2_calibrating: bool,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub date_setup: Epoch,
+    pub firmware: i64,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub last_setup: Epoch,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub last_status_store: Epoch,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub last_upgrade: Option<Epoch>,
+    pub module_name: Option<String>,
+    pub reachable: bool,
+    pub station_name: Option<String>,
+    #[serde(rename = "type")]
+    pub type_info: ModuleType,
+    pub wifi_status: f64,
+    pub dashboard_data: Option<HomeCoachDashboard>,
+    pub data_type: Vec<String>,
+    pub place: Option<Place>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct HomeCoachDashboard {
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_utc: Epoch,
+    #[serde(rename = "Temperature")]
+    pub temperature: Option<f64>,
+    #[serde(rename = "Humidity")]
+    pub humidity: Option<u64>,
+    #[serde(rename = "CO2")]
+    pub co2: Option<u64>,
+    #[serde(rename = "Noise")]
+    pub noise: Option<u64>,
+    #[serde(rename = "Pressure")]
+    pub pressure: Option<f64>,
+    #[serde(rename = "AbsolutePressure")]
+    pub absolute_pressure: Option<f64>,
+    pub health_idx: Option<HealthIndex>,
+    pub min_temp: Option<f64>,
+    pub max_temp: Option<f64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_min_temp: Option<Epoch>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_max_temp: Option<Epoch>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// The home coach's overall rating of the indoor climate.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "u8", into = "u8")]
+#[non_exhaustive]
+pub enum HealthIndex {
+    #[default]
+    Healthy,
+    Fine,
+    Fair,
+    Poor,
+    Unhealthy,
+    Other(u8),
+}
+
+impl From<u8> for HealthIndex {
+    fn from(n: u8) -> Self {
+        match n {
+            0 => HealthIndex::Healthy,
+            1 => HealthIndex::Fine,
+            2 => HealthIndex::Fair,
+            3 => HealthIndex::Poor,
+            4 => HealthIndex::Unhealthy,
+            _ => HealthIndex::Other(n),
+        }
+    }
+}
+
+impl From<HealthIndex> for u8 {
+    fn from(i: HealthIndex) -> Self {
+        match i {
+            HealthIndex::Healthy => 0,
+            HealthIndex::Fine => 1,
+            HealthIndex::Fair => 2,
+            HealthIndex::Poor => 3,
+            HealthIndex::Unhealthy => 4,
+            HealthIndex::Other(n) => n,
+        }
+    }
+}
+
+impl fmt::Display for HealthIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            HealthIndex::Healthy => "Healthy",
+            HealthIndex::Fine => "Fine",
+            HealthIndex::Fair => "Fair",
+            HealthIndex::Poor => "Poor",
+            HealthIndex::Unhealthy => "Unhealthy",
+            HealthIndex::Other(n) => return write!(f, "Unknown ({})", n),
+        };
+        write!(f, "{}", s)
+    }
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/aircare#gethomecoachsdata
+pub async fn get_homecoachs_data(client: &NetatmoClient, device_id: &MacAddress) -> Result<HomeCoachData> {
+    let mut params: HashMap<&str, String> = HashMap::default();
+    params.insert("device_id", device_id.to_string());
+
+    client
+        .call(
+            "get_homecoachs_data",
+            "https://api.netatmo.com/api/gethomecoachsdata",
+            &params,
+        )
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod get_homecoachs_data {
+        use super::*;
+
+        #[test]
+        fn parse_response() {
+            let json = r#"{
+  "body": {
+    "devices": [
+      {
+        "_id": "12:34:56:78:90:AB",
+        "co2_calibrating": false,
+        "dashboard_data": {
+          "AbsolutePressure": 1013.3,
+          "CO2": 455,
+          "Humidity": 43,
+          "Noise": 40,
+          "Pressure": 1019.3,
+          "Temperature": 20.3,
+          "health_idx": 1,
+          "time_utc": 1556451224
+        },
+        "data_type": [
+          "Temperature",
+          "CO2",
+          "Humidity",
+          "Noise",
+          "Pressure",
+          "health_idx"
+        ],
+        "date_setup": 1556295333,
+        "firmware": 140,
+        "last_setup": 1556295333,
+        "last_status_store": 1556451233,
+        "last_upgrade": 1556295520,
+        "place": {
+          "altitude": 50,
+          "city": "Alert",
+          "country": "CAN",
+          "location": [
+            82.5057837,
+            -62.5575262
+          ],
+          "timezone": "America/Toronto"
+        },
+        "reachable": true,
+        "station_name": "Home",
+        "name": "Bedroom",
+        "type": "NHC",
+        "wifi_status": 50
+      }
+    ],
+    "user": {
+      "administrative": {
+        "feel_like_algo": 0,
+        "lang": "en-US",
+        "pressureunit": 0,
+        "reg_locale": "en-US",
+        "unit": 0,
+        "windunit": 0
+      },
+      "mail": "lukas at my_domain"
+    }
+  },
+  "status": "ok",
+  "time_exec": 0.13046002388,
+  "time_server": 1556451492
+}"#;
+
+            let homecoachs_data: ::std::result::Result<HomeCoachData, _> = serde_json::from_str(json);
+
+            assert_eq!(
+                homecoachs_data.unwrap().body.devices[0]
+                    .dashboard_data
+                    .as_ref()
+                    .and_then(|x| x.health_idx),
+                Some(HealthIndex::Fine)
+            );
+        }
+    }
+}
@@ -1,43 +1,133 @@
+use super::{
+    comfort::FeelLikeAlgorithm,
+    format::UnitSystem,
+    geo::Coordinates,
+    ids::{HomeId, ModuleId, PersonId, RoomId, ScheduleId},
+    module_type::ModuleType,
+    room_type::RoomType,
+    timestamp::{epoch, Epoch, TimeZone},
+    units::{PressureUnit, WindUnit},
+};
 use crate::{
     client::NetatmoClient,
     errors::{NetatmoError, Result},
 };
 use serde::{Deserialize, Serialize};
-use serde_repr::*;
+use serde_json::Value;
 use std::{collections::HashMap, fmt, str::FromStr};
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct HomesData {
     pub body: HomesDataBody,
     pub status: String,
     pub time_exec: f64,
-    pub time_server: i64,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct HomesDataBody {
     pub homes: Option<Vec<Home>>,
     pub user: User,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Home {
-    pub id: String,
+    pub id: HomeId,
     pub name: String,
-    pub timezone: String,
+    /// In meters
+    pub altitude: Option<i64>,
+    pub coordinates: Option<Coordinates>,
+    pub country: Option<String>,
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub timezone: TimeZone,
     pub rooms: Option<Vec<Room>>,
     pub modules: Option<Vec<Module>>,
     pub therm_setpoint_default_duration: Option<i64>,
     pub therm_mode: Option<ThermMode>,
     pub schedules: Option<Vec<Schedule>>,
+    pub persons: Option<Vec<Person>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Person {
+    pub id: PersonId,
+    pub pseudo: Option<String>,
+    #[serde(rename = "url")]
+    pub face_url: Option<String>,
+}
+
+impl Home {
+    pub fn modules_in_room(&self, room_id: &RoomId) -> Vec<&Module> {
+        self.modules
+            .iter()
+            .flatten()
+            .filter(|x| x.room_id.as_ref() == Some(room_id))
+            .collect()
+    }
+
+    /// The rooms with a thermostat, valve or electric radiator, whose setpoint can be controlled.
+    pub fn heated_rooms(&self) -> Vec<&Room> {
+        self.rooms
+            .iter()
+            .flatten()
+            .filter(|room| self.modules_in_room(&room.id).iter().any(|x| x.type_field.is_heating()))
+            .collect()
+    }
+}
+
+impl Person {
+    /// Unknown faces seen by a camera are listed without a name until the user names them in the app.
+    pub fn is_known(&self) -> bool {
+        self.pseudo.is_some()
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
 pub enum ThermMode {
     #[default]
     Schedule,
     Away,
     FrostGuard,
+    Other(String),
+}
+
+impl fmt::Display for ThermMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            ThermMode::Schedule => "schedule",
+            ThermMode::Away => "away",
+            ThermMode::FrostGuard => "hg",
+            ThermMode::Other(s) => s,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl From<String> for ThermMode {
+    fn from(s: String) -> Self {
+        s.parse().unwrap_or(ThermMode::Other(s))
+    }
+}
+
+impl From<ThermMode> for String {
+    fn from(m: ThermMode) -> Self {
+        m.to_string()
+    }
 }
 
 impl FromStr for ThermMode {
@@ -53,34 +143,55 @@ impl FromStr for ThermMode {
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Room {
-    pub id: String,
+    pub id: RoomId,
     pub name: String,
     #[serde(rename = "type")]
-    pub type_field: String,
-    pub module_ids: Option<Vec<String>>,
+    pub type_field: RoomType,
+    pub module_ids: Option<Vec<ModuleId>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Module {
-    pub id: String,
+    pub id: ModuleId,
     #[serde(rename = "type")]
-    pub type_field: String,
+    pub type_field: ModuleType,
     pub name: String,
-    pub setup_date: i64,
-    pub modules_bridged: Option<Vec<String>>,
-    pub room_id: Option<String>,
-    pub bridge: Option<String>,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub setup_date: Epoch,
+    pub modules_bridged: Option<Vec<ModuleId>>,
+    pub room_id: Option<RoomId>,
+    pub bridge: Option<ModuleId>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Timetable {
     pub zone_id: i64,
     pub m_offset: i64,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+impl Timetable {
+    /// Switches to the zone `m_offset` minutes after the start of the week, on Monday at midnight.
+    pub fn new(zone_id: i64, m_offset: i64) -> Self {
+        Timetable { zone_id, m_offset }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Zone {
     pub name: String,
     pub id: i64,
@@ -89,21 +200,68 @@ pub struct Zone {
     pub rooms: Option<Vec<RoomTemp>>,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize_repr, Deserialize_repr)]
-#[repr(u8)]
+impl Zone {
+    pub fn new(id: i64, name: &str, type_field: ZoneType, rooms: &[RoomTemp]) -> Self {
+        Zone {
+            name: name.to_string(),
+            id,
+            type_field,
+            rooms: Some(rooms.to_vec()),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "u8", into = "u8")]
+#[non_exhaustive]
 pub enum ZoneType {
     #[default]
-    Day = 0,
-    Night = 1,
-    Away = 2,
-    FrostGuard = 3,
-    Custom = 4,
-    Eco = 5,
-    Comfort = 8,
+    Day,
+    Night,
+    Away,
+    FrostGuard,
+    Custom,
+    Eco,
+    Comfort,
+    Other(u8),
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+impl From<u8> for ZoneType {
+    fn from(n: u8) -> Self {
+        match n {
+            0 => ZoneType::Day,
+            1 => ZoneType::Night,
+            2 => ZoneType::Away,
+            3 => ZoneType::FrostGuard,
+            4 => ZoneType::Custom,
+            5 => ZoneType::Eco,
+            8 => ZoneType::Comfort,
+            _ => ZoneType::Other(n),
+        }
+    }
+}
+
+impl From<ZoneType> for u8 {
+    fn from(t: ZoneType) -> Self {
+        match t {
+            ZoneType::Day => 0,
+            ZoneType::Night => 1,
+            ZoneType::Away => 2,
+            ZoneType::FrostGuard => 3,
+            ZoneType::Custom => 4,
+            ZoneType::Eco => 5,
+            ZoneType::Comfort => 8,
+            ZoneType::Other(n) => n,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Schedule {
+    pub id: ScheduleId,
     pub timetable: Option<Vec<Timetable>>,
     pub zones: Option<Vec<Zone>>,
     pub name: String,
@@ -114,27 +272,40 @@ pub struct Schedule {
     pub type_field: String,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct RoomTemp {
-    pub id: String,
+    pub id: RoomId,
     pub therm_setpoint_temperature: f64,
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+impl RoomTemp {
+    pub fn new(id: &RoomId, therm_setpoint_temperature: f64) -> Self {
+        RoomTemp {
+            id: id.clone(),
+            therm_setpoint_temperature,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct User {
     pub email: String,
     pub language: String,
     pub locale: String,
-    pub feel_like_algorithm: i64,
-    pub unit_pressure: i64,
-    pub unit_system: i64,
-    pub unit_wind: i64,
+    pub feel_like_algorithm: FeelLikeAlgorithm,
+    pub unit_pressure: PressureUnit,
+    pub unit_system: UnitSystem,
+    pub unit_wind: WindUnit,
     pub id: String,
 }
 
-#[derive(Default)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
 pub struct GetHomesDataParameters {
-    home_id: Option<String>,
+    home_id: Option<HomeId>,
     gateway_types: Option<Vec<GatewayType>>,
 }
 
@@ -143,9 +314,9 @@ impl GetHomesDataParameters {
         GetHomesDataParameters::default()
     }
 
-    pub fn home_id(self, home_id: &str) -> Self {
+    pub fn home_id(self, home_id: &HomeId) -> Self {
         GetHomesDataParameters {
-            home_id: Some(home_id.to_string()),
+            home_id: Some(home_id.clone()),
             ..self
         }
     }
@@ -159,11 +330,20 @@ impl GetHomesDataParameters {
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+#[non_exhaustive]
 pub enum GatewayType {
     #[default]
     NAPlug,
     OTH,
     BNS,
+    NACamera,
+    NOC,
+    NDB,
+    NSD,
+    NCO,
+    NMG,
 }
 
 impl fmt::Display for GatewayType {
@@ -172,17 +352,59 @@ impl fmt::Display for GatewayType {
             GatewayType::NAPlug => "NAPlug",
             GatewayType::OTH => "OTH",
             GatewayType::BNS => "BNS",
+            GatewayType::NACamera => "NACamera",
+            GatewayType::NOC => "NOC",
+            GatewayType::NDB => "NDB",
+            GatewayType::NSD => "NSD",
+            GatewayType::NCO => "NCO",
+            GatewayType::NMG => "NMG",
         };
         write!(f, "{}", s)
     }
 }
 
+impl FromStr for GatewayType {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "NAPlug" => Ok(GatewayType::NAPlug),
+            "OTH" => Ok(GatewayType::OTH),
+            "BNS" => Ok(GatewayType::BNS),
+            "NACamera" => Ok(GatewayType::NACamera),
+            "NOC" => Ok(GatewayType::NOC),
+            "NDB" => Ok(GatewayType::NDB),
+            "NSD" => Ok(GatewayType::NSD),
+            "NCO" => Ok(GatewayType::NCO),
+            "NMG" => Ok(GatewayType::NMG),
+            _ => Err(NetatmoError::invalid_parameter(
+                "gateway_types",
+                &format!("unknown value '{}'", s),
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for GatewayType {
+    type Error = NetatmoError;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<GatewayType> for String {
+    fn from(t: GatewayType) -> Self {
+        t.to_string()
+    }
+}
+
 #[allow(clippy::implicit_hasher)]
-impl From<&GetHomesDataParameters> for HashMap<String, String> {
-    fn from(p: &GetHomesDataParameters) -> HashMap<String, String> {
+impl From<&GetHomesDataParameters> for HashMap<&'static str, String> {
+    fn from(p: &GetHomesDataParameters) -> HashMap<&'static str, String> {
         let mut map = HashMap::default();
         if let Some(home_id) = &p.home_id {
-            map.insert("home_id".to_string(), home_id.to_string());
+            map.insert("home_id", home_id.to_string());
         }
         if let Some(gateway_types) = &p.gateway_types {
             let gateway_types = gateway_types
@@ -191,7 +413,7 @@ impl From<&GetHomesDataParameters> for HashMap<String, String> {
                 .collect::<Vec<_>>()
                 .as_slice()
                 .join(",");
-            map.insert("gateway_types".to_string(), gateway_types);
+            map.insert("gateway_types", gateway_types);
         }
 
         map
@@ -199,16 +421,16 @@ impl From<&GetHomesDataParameters> for HashMap<String, String> {
 }
 
 pub async fn get_homes_data(client: &NetatmoClient, parameters: &GetHomesDataParameters) -> Result<HomesData> {
-    let params: HashMap<String, String> = parameters.into();
-    let mut params = params.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    let params: HashMap<&str, String> = parameters.into();
     client
-        .call("get_homes_data", "https://api.netatmo.com/api/homesdata", &mut params)
+        .call("get_homes_data", "https://api.netatmo.com/api/homesdata", &params)
         .await
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::client::test_util::{assert_round_trip, homes_data};
 
     mod get_homes_data {
         use super::*;
@@ -227,7 +449,7 @@ mod test {
                         -62.5575262
                       ],
                       "country": "CAN",
-                      "timezone": "EDT",
+                      "timezone": "America/Toronto",
                       "rooms": [
                         {
                           "id": "...",
@@ -235,6 +457,16 @@ mod test {
                           "type": "bedroom"
                         }
                       ],
+                      "persons": [
+                        {
+                          "id": "...",
+                          "pseudo": "John",
+                          "url": "https://netatmocameraimage.blob.core.windows.net/production/..."
+                        },
+                        {
+                          "id": "..."
+                        }
+                      ],
                       "schedules": [
                         {
                           "timetable": [
@@ -439,6 +671,71 @@ mod test {
             let homes_data: std::result::Result<HomesData, _> = serde_json::from_str(json);
 
             assert!(&homes_data.is_ok());
+
+            let home = &homes_data.unwrap().body.homes.unwrap()[0];
+            assert_eq!(home.altitude, Some(50));
+            assert_eq!(home.coordinates, Some(Coordinates::new(-62.5575262, 82.5057837)));
+            assert_eq!(home.country.as_deref(), Some("CAN"));
+        }
+
+        #[test]
+        fn parse_unknown_values() {
+            let therm_mode: ThermMode = serde_json::from_str(r#""something_new""#).unwrap();
+            let zone_type: ZoneType = serde_json::from_str("9").unwrap();
+
+            assert_eq!(therm_mode, ThermMode::Other("something_new".to_string()));
+            assert_eq!(zone_type, ZoneType::Other(9));
+            assert_eq!(
+                serde_json::from_str::<ThermMode>(r#""hg""#).unwrap(),
+                ThermMode::FrostGuard
+            );
+            assert_eq!(serde_json::to_string(&ZoneType::Comfort).unwrap(), "8");
+        }
+
+        #[test]
+        fn enum_round_trip() {
+            assert_round_trip!(
+                GatewayType::NAPlug,
+                GatewayType::OTH,
+                GatewayType::BNS,
+                GatewayType::NACamera,
+                GatewayType::NOC,
+                GatewayType::NDB,
+                GatewayType::NSD,
+                GatewayType::NCO,
+                GatewayType::NMG,
+            );
+
+            assert_round_trip!(ThermMode::Schedule, ThermMode::Away, ThermMode::FrostGuard);
+        }
+    }
+
+    mod home {
+        use super::*;
+
+        #[test]
+        fn heated_rooms() {
+            let home = Home {
+                rooms: Some(vec![
+                    homes_data::room("1", "Living room", RoomType::LivingRoom),
+                    homes_data::room("2", "Kitchen", RoomType::Kitchen),
+                ]),
+                modules: Some(vec![
+                    Module {
+                        room_id: Some(RoomId::new("1")),
+                        ..homes_data::module("70:ee:50:00:00:01", ModuleType::NMR, "Radiator")
+                    },
+                    Module {
+                        room_id: Some(RoomId::new("2")),
+                        ..homes_data::module("70:ee:50:00:00:02", ModuleType::NLP, "Kettle")
+                    },
+                ]),
+                ..homes_data::home("home", "Home", "Europe/Paris")
+            };
+
+            let rooms: Vec<&str> = home.heated_rooms().iter().map(|x| x.id.as_str()).collect();
+
+            assert_eq!(rooms, vec!["1"]);
         }
     }
 }
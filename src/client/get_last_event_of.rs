@@ -0,0 +1,121 @@
+use super::{
+    get_events::{de_events, Event},
+    ids::{HomeId, PersonId},
+    timestamp::{epoch, Epoch},
+};
+use crate::{client::NetatmoClient, errors::Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct LastEventOf {
+    pub status: String,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+    pub body: LastEventOfBody,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct LastEventOfBody {
+    #[serde(default, deserialize_with = "de_events")]
+    pub events_list: Vec<Event>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GetLastEventOfParameters {
+    home_id: HomeId,
+    person_id: PersonId,
+    offset: Option<usize>,
+}
+
+impl GetLastEventOfParameters {
+    pub fn new(home_id: &HomeId, person_id: &PersonId) -> Self {
+        GetLastEventOfParameters {
+            home_id: home_id.clone(),
+            person_id: person_id.clone(),
+            offset: None,
+        }
+    }
+
+    pub fn home_id(&self) -> &HomeId {
+        &self.home_id
+    }
+
+    pub fn person_id(&self) -> &PersonId {
+        &self.person_id
+    }
+
+    pub fn offset(self, offset: usize) -> Self {
+        GetLastEventOfParameters {
+            offset: Some(offset),
+            ..self
+        }
+    }
+}
+
+#[allow(clippy::implicit_hasher)]
+impl From<&GetLastEventOfParameters> for HashMap<&'static str, String> {
+    fn from(p: &GetLastEventOfParameters) -> HashMap<&'static str, String> {
+        let mut map = HashMap::default();
+        map.insert("home_id", p.home_id.to_string());
+        map.insert("person_id", p.person_id.to_string());
+        if let Some(offset) = p.offset {
+            map.insert("offset", offset.to_string());
+        }
+
+        map
+    }
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/security#getlasteventof
+pub async fn get_last_event_of(client: &NetatmoClient, parameters: &GetLastEventOfParameters) -> Result<LastEventOf> {
+    let params: HashMap<&str, String> = parameters.into();
+    client
+        .call(
+            "get_last_event_of",
+            "https://api.netatmo.com/api/getlasteventof",
+            &params,
+        )
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod get_last_event_of {
+        use super::*;
+
+        #[test]
+        fn parse_response() {
+            let json = r#"{
+                "body": {
+                  "events_list": [
+                    {
+                      "id": "...",
+                      "type": "person",
+                      "time": 1689865621,
+                      "module_id": "70:ee:50:00:00:01",
+                      "message": "<b>John</b> seen",
+                      "person_id": "...",
+                      "is_arrival": true,
+                      "video_id": "...",
+                      "video_status": "available"
+                    }
+                  ]
+                },
+                "status": "ok",
+                "time_server": 1689865700
+              }"#;
+
+            let last_event_of: std::result::Result<LastEventOf, _> = serde_json::from_str(json);
+
+            assert!(&last_event_of.is_ok());
+        }
+    }
+}
@@ -1,59 +1,105 @@
-use crate::{client::NetatmoClient, errors::Result};
+use super::{
+    mac_address::MacAddress,
+    retry_on_rate_limit,
+    time_series::{Point, TimeSeries},
+    timestamp::Timestamp,
+};
+use crate::{
+    client::NetatmoClient,
+    errors::{NetatmoError, Result},
+};
 use serde::{Deserialize, Deserializer, Serialize};
-use std::{collections::HashMap, fmt, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    str::FromStr,
+};
 
+// The API returns at most this many values per call
+const MAX_VALUES_PER_CALL: i64 = 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct GetMeasureParameters {
-    device_id: String,
-    module_id: String,
+    device_id: MacAddress,
+    module_id: MacAddress,
     scale: Scale,
     types: Vec<Type>,
-    date_begin: Option<usize>,
-    date_end: Option<usize>,
+    date_begin: Option<i64>,
+    date_end: Option<i64>,
     limit: Option<bool>,
+    optimize: bool,
     real_time: Option<bool>,
 }
 
 impl GetMeasureParameters {
-    pub fn new(device_id: &str, scale: Scale, types: &[Type]) -> Self {
+    pub fn new(device_id: &MacAddress, scale: Scale, types: &[Type]) -> Self {
         GetMeasureParameters {
-            device_id: device_id.to_string(),
-            module_id: device_id.to_string(),
+            device_id: device_id.clone(),
+            module_id: device_id.clone(),
             scale,
             types: types.to_vec(),
             date_begin: None,
             date_end: None,
             limit: None,
+            optimize: false,
             real_time: None,
         }
     }
 
-    pub fn with_module_id(device_id: &str, module_id: &str, scale: Scale, types: &[Type]) -> Self {
+    pub fn with_module_id(device_id: &MacAddress, module_id: &MacAddress, scale: Scale, types: &[Type]) -> Self {
         GetMeasureParameters {
-            device_id: device_id.to_string(),
-            module_id: module_id.to_string(),
+            device_id: device_id.clone(),
+            module_id: module_id.clone(),
             scale,
             types: types.to_vec(),
             date_begin: None,
             date_end: None,
             limit: None,
+            optimize: false,
             real_time: None,
         }
     }
 
-    pub fn date_begin(self, date_begin: usize) -> Self {
+    pub fn device_id(&self) -> &MacAddress {
+        &self.device_id
+    }
+
+    pub fn module_id(&self) -> &MacAddress {
+        &self.module_id
+    }
+
+    pub fn scale(&self) -> &Scale {
+        &self.scale
+    }
+
+    pub fn types(&self) -> &[Type] {
+        &self.types
+    }
+
+    pub fn date_begin(self, date_begin: i64) -> Self {
         GetMeasureParameters {
             date_begin: Some(date_begin),
             ..self
         }
     }
 
-    pub fn date_end(self, date_end: usize) -> Self {
+    /// Like `date_begin`, from e.g. a `SystemTime` or, with the `chrono` feature, a `DateTime`.
+    pub fn date_begin_at<T: Timestamp>(self, date_begin: T) -> Self {
+        self.date_begin(date_begin.epoch_seconds())
+    }
+
+    pub fn date_end(self, date_end: i64) -> Self {
         GetMeasureParameters {
             date_end: Some(date_end),
             ..self
         }
     }
 
+    /// Like `date_end`, from e.g. a `SystemTime` or, with the `chrono` feature, a `DateTime`.
+    pub fn date_end_at<T: Timestamp>(self, date_end: T) -> Self {
+        self.date_end(date_end.epoch_seconds())
+    }
+
     pub fn limit(self, limit: bool) -> Self {
         GetMeasureParameters {
             limit: Some(limit),
@@ -61,15 +107,37 @@ impl GetMeasureParameters {
         }
     }
 
+    /// Requests the compact response format, which is expanded into the same values on deserialization.
+    pub fn optimize(self, optimize: bool) -> Self {
+        GetMeasureParameters { optimize, ..self }
+    }
+
     pub fn real_time(self, real_time: bool) -> Self {
         GetMeasureParameters {
             real_time: Some(real_time),
             ..self
         }
     }
+
+    /// Checks the combination of parameters the API would reject, which `get_measure` does before calling it.
+    pub fn validate(&self) -> Result<()> {
+        if self.types.is_empty() {
+            return Err(NetatmoError::invalid_parameter("type", "at least one type is required"));
+        }
+        if let (Some(date_begin), Some(date_end)) = (self.date_begin, self.date_end) {
+            if date_end < date_begin {
+                return Err(NetatmoError::invalid_parameter("date_end", "is before date_begin"));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+#[non_exhaustive]
 pub enum Scale {
     #[default]
     Max,
@@ -81,6 +149,21 @@ pub enum Scale {
     Month1,
 }
 
+impl Scale {
+    /// The nominal interval between two values in seconds; `Max` is the stations' 5 minute measurement interval.
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Scale::Max => 5 * 60,
+            Scale::Min30 => 30 * 60,
+            Scale::Hour1 => 60 * 60,
+            Scale::Hours3 => 3 * 60 * 60,
+            Scale::Day1 => 24 * 60 * 60,
+            Scale::Week1 => 7 * 24 * 60 * 60,
+            Scale::Month1 => 31 * 24 * 60 * 60,
+        }
+    }
+}
+
 impl fmt::Display for Scale {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let s = match self {
@@ -96,12 +179,84 @@ impl fmt::Display for Scale {
     }
 }
 
+impl FromStr for Scale {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "max" => Ok(Scale::Max),
+            "30min" => Ok(Scale::Min30),
+            "1hour" => Ok(Scale::Hour1),
+            "3hours" => Ok(Scale::Hours3),
+            "1day" => Ok(Scale::Day1),
+            "1week" => Ok(Scale::Week1),
+            "1month" => Ok(Scale::Month1),
+            _ => Err(NetatmoError::invalid_parameter(
+                "scale",
+                &format!("unknown value '{}'", s),
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for Scale {
+    type Error = NetatmoError;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<Scale> for String {
+    fn from(scale: Scale) -> Self {
+        scale.to_string()
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+#[non_exhaustive]
 pub enum Type {
     #[default]
     Temperature,
     Humidity,
     CO2,
+    Pressure,
+    Noise,
+    Rain,
+    SumRain,
+    WindStrength,
+    WindAngle,
+    GustStrength,
+    GustAngle,
+    MinTemp,
+    MaxTemp,
+    MinHum,
+    MaxHum,
+    MinPressure,
+    MaxPressure,
+    MinNoise,
+    MaxNoise,
+    MinCO2,
+    MaxCO2,
+    DateMinTemp,
+    DateMaxTemp,
+    DateMinHum,
+    DateMaxHum,
+    DateMinPressure,
+    DateMaxPressure,
+    DateMinNoise,
+    DateMaxNoise,
+    DateMinCO2,
+    DateMaxCO2,
+    DateMaxGust,
+    /// Electricity consumed by an energy meter or plug, in Wh
+    SumEnergyElec,
+    /// Electricity consumed during off-peak hours, for meters on a two-rate tariff
+    SumEnergyElecOffPeak,
+    /// Electricity consumed during peak hours, for meters on a two-rate tariff
+    SumEnergyElecPeak,
 }
 
 impl fmt::Display for Type {
@@ -110,14 +265,108 @@ impl fmt::Display for Type {
             Type::Temperature => "Temperature",
             Type::Humidity => "Humidity",
             Type::CO2 => "CO2",
+            Type::Pressure => "Pressure",
+            Type::Noise => "Noise",
+            Type::Rain => "Rain",
+            Type::SumRain => "sum_rain",
+            Type::WindStrength => "WindStrength",
+            Type::WindAngle => "WindAngle",
+            Type::GustStrength => "GustStrength",
+            Type::GustAngle => "GustAngle",
+            Type::MinTemp => "min_temp",
+            Type::MaxTemp => "max_temp",
+            Type::MinHum => "min_hum",
+            Type::MaxHum => "max_hum",
+            Type::MinPressure => "min_pressure",
+            Type::MaxPressure => "max_pressure",
+            Type::MinNoise => "min_noise",
+            Type::MaxNoise => "max_noise",
+            Type::MinCO2 => "min_co2",
+            Type::MaxCO2 => "max_co2",
+            Type::DateMinTemp => "date_min_temp",
+            Type::DateMaxTemp => "date_max_temp",
+            Type::DateMinHum => "date_min_hum",
+            Type::DateMaxHum => "date_max_hum",
+            Type::DateMinPressure => "date_min_pressure",
+            Type::DateMaxPressure => "date_max_pressure",
+            Type::DateMinNoise => "date_min_noise",
+            Type::DateMaxNoise => "date_max_noise",
+            Type::DateMinCO2 => "date_min_co2",
+            Type::DateMaxCO2 => "date_max_co2",
+            Type::DateMaxGust => "date_max_gust",
+            Type::SumEnergyElec => "sum_energy_elec",
+            Type::SumEnergyElecOffPeak => "sum_energy_elec$0",
+            Type::SumEnergyElecPeak => "sum_energy_elec$1",
         };
         write!(f, "{}", s)
     }
 }
 
+impl FromStr for Type {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "Temperature" => Ok(Type::Temperature),
+            "Humidity" => Ok(Type::Humidity),
+            "CO2" => Ok(Type::CO2),
+            "Pressure" => Ok(Type::Pressure),
+            "Noise" => Ok(Type::Noise),
+            "Rain" => Ok(Type::Rain),
+            "sum_rain" => Ok(Type::SumRain),
+            "WindStrength" => Ok(Type::WindStrength),
+            "WindAngle" => Ok(Type::WindAngle),
+            "GustStrength" => Ok(Type::GustStrength),
+            "GustAngle" => Ok(Type::GustAngle),
+            "min_temp" => Ok(Type::MinTemp),
+            "max_temp" => Ok(Type::MaxTemp),
+            "min_hum" => Ok(Type::MinHum),
+            "max_hum" => Ok(Type::MaxHum),
+            "min_pressure" => Ok(Type::MinPressure),
+            "max_pressure" => Ok(Type::MaxPressure),
+            "min_noise" => Ok(Type::MinNoise),
+            "max_noise" => Ok(Type::MaxNoise),
+            "min_co2" => Ok(Type::MinCO2),
+            "max_co2" => Ok(Type::MaxCO2),
+            "date_min_temp" => Ok(Type::DateMinTemp),
+            "date_max_temp" => Ok(Type::DateMaxTemp),
+            "date_min_hum" => Ok(Type::DateMinHum),
+            "date_max_hum" => Ok(Type::DateMaxHum),
+            "date_min_pressure" => Ok(Type::DateMinPressure),
+            "date_max_pressure" => Ok(Type::DateMaxPressure),
+            "date_min_noise" => Ok(Type::DateMinNoise),
+            "date_max_noise" => Ok(Type::DateMaxNoise),
+            "date_min_co2" => Ok(Type::DateMinCO2),
+            "date_max_co2" => Ok(Type::DateMaxCO2),
+            "date_max_gust" => Ok(Type::DateMaxGust),
+            "sum_energy_elec" => Ok(Type::SumEnergyElec),
+            "sum_energy_elec$0" => Ok(Type::SumEnergyElecOffPeak),
+            "sum_energy_elec$1" => Ok(Type::SumEnergyElecPeak),
+            _ => Err(NetatmoError::invalid_parameter(
+                "type",
+                &format!("unknown value '{}'", s),
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for Type {
+    type Error = NetatmoError;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<Type> for String {
+    fn from(t: Type) -> Self {
+        t.to_string()
+    }
+}
+
 #[allow(clippy::implicit_hasher)]
-impl From<&GetMeasureParameters> for HashMap<String, String> {
-    fn from(p: &GetMeasureParameters) -> HashMap<String, String> {
+impl From<&GetMeasureParameters> for HashMap<&'static str, String> {
+    fn from(p: &GetMeasureParameters) -> HashMap<&'static str, String> {
         let types = p
             .types
             .iter()
@@ -126,57 +375,165 @@ impl From<&GetMeasureParameters> for HashMap<String, String> {
             .as_slice()
             .join(",");
         let mut m = HashMap::default();
-        m.insert("device_id".to_string(), p.device_id.to_string());
-        m.insert("module_id".to_string(), p.module_id.to_string());
-        m.insert("scale".to_string(), p.scale.to_string());
-        m.insert("type".to_string(), types);
+        m.insert("device_id", p.device_id.to_string());
+        m.insert("module_id", p.module_id.to_string());
+        m.insert("scale", p.scale.to_string());
+        m.insert("type", types);
         if let Some(date_begin) = p.date_begin {
-            m.insert("date_begin".to_string(), date_begin.to_string());
+            m.insert("date_begin", date_begin.to_string());
         }
         if let Some(date_end) = p.date_end {
-            m.insert("date_end".to_string(), date_end.to_string());
+            m.insert("date_end", date_end.to_string());
         }
         if let Some(limit) = p.limit {
-            m.insert("limit".to_string(), limit.to_string());
+            m.insert("limit", limit.to_string());
         }
-        m.insert("optimize".to_string(), "false".to_string());
+        m.insert("optimize", p.optimize.to_string());
         if let Some(real_time) = p.real_time {
-            m.insert("real_time".to_string(), real_time.to_string());
+            m.insert("real_time", real_time.to_string());
         }
 
         m
     }
 }
 
+/// Fetches measurements and labels them with the requested types.
+pub async fn get_measure_series(client: &NetatmoClient, parameters: &GetMeasureParameters) -> Result<TimeSeries> {
+    get_measure(client, parameters).await.map(|x| x.time_series())
+}
+
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Measure {
     status: String,
     time_exec: f64,
     #[serde(rename = "body", deserialize_with = "de_body_values")]
-    values: HashMap<usize, Vec<Option<f64>>>,
+    #[cfg_attr(feature = "schemars", schemars(with = "serde_json::Value"))]
+    values: BTreeMap<i64, Vec<Option<f64>>>,
+    /// The response doesn't name the types of the values, `get_measure` sets the requested ones; they are serialized
+    /// so that a stored measure keeps its labels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    types: Vec<Type>,
+}
+
+impl Measure {
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn time_exec(&self) -> f64 {
+        self.time_exec
+    }
+
+    /// The types the values were requested as.
+    pub fn types(&self) -> &[Type] {
+        &self.types
+    }
+
+    /// The values by time, each labeled by its type, e.g. `point.get(&Type::Temperature)`.
+    pub fn points(&self) -> impl Iterator<Item = Point<'_>> {
+        self.values
+            .iter()
+            .map(|(timestamp, values)| Point::new(*timestamp, &self.types, values))
+    }
+
+    pub fn time_series(&self) -> TimeSeries {
+        TimeSeries::new(&self.types, self.values.clone())
+    }
 }
 
 // cf. https://dev.netatmo.com/resources/technical/reference/common/getmeasure
 pub async fn get_measure(client: &NetatmoClient, parameters: &GetMeasureParameters) -> Result<Measure> {
-    let params: HashMap<String, String> = parameters.into();
-    let mut params = params.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    parameters.validate()?;
+    let params: HashMap<&str, String> = parameters.into();
 
-    client
-        .call("get_measure", "https://api.netatmo.com/api/getmeasure", &mut params)
-        .await
+    let measure: Measure = client
+        .call("get_measure", "https://api.netatmo.com/api/getmeasure", &params)
+        .await?;
+
+    Ok(Measure {
+        types: parameters.types.clone(),
+        ..measure
+    })
 }
 
-fn de_body_values<'de, D>(deserializer: D) -> ::std::result::Result<HashMap<usize, Vec<Option<f64>>>, D::Error>
+/// Fetches all values between `start` and `end`, splitting the range into as many calls as the API's limit of values
+/// per call requires. Calls hitting the rate limit are retried with a backoff.
+pub async fn get_measure_range(
+    client: &NetatmoClient,
+    parameters: &GetMeasureParameters,
+    start: i64,
+    end: i64,
+) -> Result<TimeSeries> {
+    let window = parameters.scale.seconds() * MAX_VALUES_PER_CALL;
+    let mut series = TimeSeries::new(&parameters.types, vec![]);
+    let mut cursor = start;
+
+    while let Some((chunk_begin, chunk_end)) = chunk(cursor, end, window) {
+        let chunk_parameters = parameters.clone().date_begin(chunk_begin).date_end(chunk_end);
+        let measure = retry_on_rate_limit(|| get_measure(client, &chunk_parameters)).await?;
+
+        cursor = next_cursor(chunk_begin, chunk_end, measure.values.keys().next_back().copied());
+        series.extend(measure.time_series());
+    }
+
+    Ok(series)
+}
+
+/// The range of the next call starting at `cursor`, spanning `window` seconds or up to `end`.
+fn chunk(cursor: i64, end: i64, window: i64) -> Option<(i64, i64)> {
+    (cursor <= end).then(|| (cursor, end.min(cursor + window - 1)))
+}
+
+/// Where the call after the one for `chunk_begin..=chunk_end` starts, given the timestamp of the last value received.
+///
+/// It continues after that value in case the API returned fewer values than the range spans, and after the range if
+/// there was none.
+fn next_cursor(chunk_begin: i64, chunk_end: i64, last: Option<i64>) -> i64 {
+    match last {
+        Some(last) if last >= chunk_begin && last < chunk_end => last + 1,
+        _ => chunk_end + 1,
+    }
+}
+
+fn de_body_values<'de, D>(deserializer: D) -> ::std::result::Result<BTreeMap<i64, Vec<Option<f64>>>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let map = HashMap::<String, Vec<Option<f64>>>::deserialize(deserializer)?;
-    let mut tuples = Vec::new();
-    for (k, v) in map {
-        let key = usize::from_str(&k).map_err(serde::de::Error::custom)?;
-        tuples.push((key, v));
+    // With optimize=false, the body maps each timestamp to its values
+    // With optimize=true, the body is a list of chunks of equidistant values, starting at beg_time
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Body {
+        Values(HashMap<String, Vec<Option<f64>>>),
+        Optimized(Vec<OptimizedChunk>),
+    }
+
+    #[derive(Deserialize)]
+    struct OptimizedChunk {
+        beg_time: i64,
+        step_time: Option<i64>,
+        value: Vec<Vec<Option<f64>>>,
+    }
+
+    let mut res = BTreeMap::new();
+    match Body::deserialize(deserializer)? {
+        Body::Values(map) => {
+            for (k, v) in map {
+                let key = i64::from_str(&k).map_err(serde::de::Error::custom)?;
+                res.insert(key, v);
+            }
+        }
+        Body::Optimized(chunks) => {
+            for chunk in chunks {
+                let step_time = chunk.step_time.unwrap_or_default();
+                for (i, v) in chunk.value.into_iter().enumerate() {
+                    res.insert(chunk.beg_time + i as i64 * step_time, v);
+                }
+            }
+        }
     }
-    let res = tuples.into_iter().collect();
 
     Ok(res)
 }
@@ -184,6 +541,7 @@ where
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::client::test_util::assert_round_trip;
 
     mod get_measure {
         use super::*;
@@ -209,5 +567,135 @@ mod test {
 
             assert!(&measure.is_ok());
         }
+
+        #[test]
+        fn parse_optimized_response() {
+            let json = r#"{
+                "body": [
+                  {
+                    "beg_time": 1623794400,
+                    "step_time": 600,
+                    "value": [
+                      [
+                        21.5,
+                        45
+                      ],
+                      [
+                        21.4,
+                        46
+                      ]
+                    ]
+                  },
+                  {
+                    "beg_time": 1626386400,
+                    "value": [
+                      [
+                        20.1,
+                        null
+                      ]
+                    ]
+                  }
+                ],
+                "status": "ok",
+                "time_exec": 0.039312124252319336,
+                "time_server": 1689866240
+              }"#;
+
+            let measure = Measure {
+                types: vec![Type::Temperature, Type::Humidity],
+                ..serde_json::from_str(json).unwrap()
+            };
+
+            assert_eq!(measure.status(), "ok");
+            let points: Vec<Point> = measure.points().collect();
+            assert_eq!(points.len(), 3);
+            assert_eq!(points[1].timestamp, 1623795000);
+            assert_eq!(points[1].get(&Type::Temperature), Some(21.4));
+            assert_eq!(points[1].get(&Type::Humidity), Some(46.0));
+            assert_eq!(points[2].get(&Type::Humidity), None);
+            assert_eq!(points[2].get(&Type::CO2), None);
+
+            let stored: Measure = serde_json::from_str(&serde_json::to_string(&measure).unwrap()).unwrap();
+            assert_eq!(stored.types(), [Type::Temperature, Type::Humidity]);
+            assert_eq!(stored.points().nth(1).unwrap().get(&Type::Humidity), Some(46.0));
+        }
+
+        #[test]
+        fn scale_and_type_round_trip() {
+            assert_round_trip!(
+                Scale::Max,
+                Scale::Min30,
+                Scale::Hour1,
+                Scale::Hours3,
+                Scale::Day1,
+                Scale::Week1,
+                Scale::Month1,
+            );
+
+            assert_round_trip!(
+                Type::Temperature,
+                Type::CO2,
+                Type::SumRain,
+                Type::WindStrength,
+                Type::MinTemp,
+                Type::DateMaxGust,
+                Type::SumEnergyElec,
+                Type::SumEnergyElecOffPeak,
+                Type::SumEnergyElecPeak,
+            );
+            assert!("5min".parse::<Scale>().is_err());
+        }
+    }
+
+    mod get_measure_range {
+        use super::*;
+
+        const STEP: i64 = 30 * 60;
+        const WINDOW: i64 = STEP * MAX_VALUES_PER_CALL;
+
+        /// The ranges of the calls for `start..=end`, with `last` returning the last value the API sends for a range.
+        fn calls(start: i64, end: i64, last: impl Fn(i64, i64) -> Option<i64>) -> Vec<(i64, i64)> {
+            let mut calls = vec![];
+            let mut cursor = start;
+            while let Some((begin, end)) = chunk(cursor, end, WINDOW) {
+                calls.push((begin, end));
+                cursor = next_cursor(begin, end, last(begin, end));
+            }
+
+            calls
+        }
+
+        /// Values every `STEP` seconds, at most `MAX_VALUES_PER_CALL` per call.
+        fn full_page(begin: i64, end: i64) -> Option<i64> {
+            let first = begin + (STEP - begin.rem_euclid(STEP)) % STEP;
+            let last = end - end.rem_euclid(STEP);
+            (first <= last).then(|| last.min(first + (MAX_VALUES_PER_CALL - 1) * STEP))
+        }
+
+        #[test]
+        fn exact_multiple_of_max_values() {
+            let end = 2 * WINDOW - STEP;
+
+            assert_eq!(calls(0, end, full_page), [(0, WINDOW - 1), (WINDOW - STEP + 1, end)]);
+        }
+
+        #[test]
+        fn short_page() {
+            let calls = calls(0, WINDOW - 1, |begin, end| {
+                full_page(begin, end).map(|x| x.min(100 * STEP))
+            });
+
+            assert_eq!(calls, [(0, WINDOW - 1), (100 * STEP + 1, WINDOW - 1)]);
+        }
+
+        #[test]
+        fn empty_page() {
+            let end = 3 * WINDOW - 1;
+
+            assert_eq!(
+                calls(0, end, |_, _| None),
+                [(0, WINDOW - 1), (WINDOW, 2 * WINDOW - 1), (2 * WINDOW, end)]
+            );
+        }
     }
 }
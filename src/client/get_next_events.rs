@@ -0,0 +1,115 @@
+use super::{
+    get_events::{de_events, Event},
+    ids::HomeId,
+    timestamp::{epoch, Epoch},
+};
+use crate::{client::NetatmoClient, errors::Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct NextEvents {
+    pub status: String,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+    pub body: NextEventsBody,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct NextEventsBody {
+    #[serde(default, deserialize_with = "de_events")]
+    pub events_list: Vec<Event>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GetNextEventsParameters {
+    home_id: HomeId,
+    event_id: String,
+    size: Option<usize>,
+}
+
+impl GetNextEventsParameters {
+    pub fn new(home_id: &HomeId, event_id: &str) -> Self {
+        GetNextEventsParameters {
+            home_id: home_id.clone(),
+            event_id: event_id.to_string(),
+            size: None,
+        }
+    }
+
+    pub fn home_id(&self) -> &HomeId {
+        &self.home_id
+    }
+
+    pub fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    pub fn size(self, size: usize) -> Self {
+        GetNextEventsParameters {
+            size: Some(size),
+            ..self
+        }
+    }
+}
+
+#[allow(clippy::implicit_hasher)]
+impl From<&GetNextEventsParameters> for HashMap<&'static str, String> {
+    fn from(p: &GetNextEventsParameters) -> HashMap<&'static str, String> {
+        let mut map = HashMap::default();
+        map.insert("home_id", p.home_id.to_string());
+        map.insert("event_id", p.event_id.to_string());
+        if let Some(size) = p.size {
+            map.insert("size", size.to_string());
+        }
+
+        map
+    }
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/security#getnextevents
+pub async fn get_next_events(client: &NetatmoClient, parameters: &GetNextEventsParameters) -> Result<NextEvents> {
+    let params: HashMap<&str, String> = parameters.into();
+    client
+        .call("get_next_events", "https://api.netatmo.com/api/getnextevents", &params)
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod get_next_events {
+        use super::*;
+
+        #[test]
+        fn parse_response() {
+            let json = r#"{
+                "body": {
+                  "events_list": [
+                    {
+                      "id": "...",
+                      "type": "movement",
+                      "time": 1689865621,
+                      "module_id": "70:ee:50:00:00:01",
+                      "message": "Motion detected",
+                      "video_id": "...",
+                      "video_status": "deleted"
+                    }
+                  ]
+                },
+                "status": "ok",
+                "time_server": 1689865700
+              }"#;
+
+            let next_events: std::result::Result<NextEvents, _> = serde_json::from_str(json);
+
+            assert!(&next_events.is_ok());
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use super::{
+    geo::Coordinates,
+    module_type::ModuleType,
+    timestamp::{epoch, Epoch, TimeZone},
+    units::WindSpeed,
+};
+use crate::{
+    client::NetatmoClient,
+    errors::{NetatmoError, Result},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::HashMap, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct PublicData {
+    pub status: String,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+    pub body: Vec<PublicStation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct PublicStation {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub place: PublicPlace,
+    pub mark: Option<i64>,
+    #[serde(default)]
+    pub measures: HashMap<String, PublicMeasure>,
+    #[serde(default)]
+    pub modules: Vec<String>,
+    pub module_types: Option<HashMap<String, ModuleType>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct PublicPlace {
+    pub location: Coordinates,
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+    pub timezone: Option<TimeZone>,
+    pub country: Option<String>,
+    pub altitude: Option<f64>,
+    pub city: Option<String>,
+    pub street: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct PublicMeasure {
+    pub res: Option<HashMap<String, Vec<Option<f64>>>>,
+    #[serde(rename = "type")]
+    pub types: Option<Vec<String>>,
+    pub rain_60min: Option<f64>,
+    pub rain_24h: Option<f64>,
+    pub rain_live: Option<f64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub rain_timeutc: Option<Epoch>,
+    pub wind_strength: Option<WindSpeed>,
+    pub wind_angle: Option<f64>,
+    pub gust_strength: Option<WindSpeed>,
+    pub gust_angle: Option<f64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub wind_timeutc: Option<Epoch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GetPublicDataParameters {
+    lat_ne: f64,
+    lon_ne: f64,
+    lat_sw: f64,
+    lon_sw: f64,
+    required_data: Option<Vec<RequiredData>>,
+    filter: Option<bool>,
+}
+
+impl GetPublicDataParameters {
+    pub fn new(lat_ne: f64, lon_ne: f64, lat_sw: f64, lon_sw: f64) -> Self {
+        GetPublicDataParameters {
+            lat_ne,
+            lon_ne,
+            lat_sw,
+            lon_sw,
+            required_data: None,
+            filter: None,
+        }
+    }
+
+    pub fn lat_ne(&self) -> f64 {
+        self.lat_ne
+    }
+
+    pub fn lon_ne(&self) -> f64 {
+        self.lon_ne
+    }
+
+    pub fn lat_sw(&self) -> f64 {
+        self.lat_sw
+    }
+
+    pub fn lon_sw(&self) -> f64 {
+        self.lon_sw
+    }
+
+    pub fn required_data(self, required_data: &[RequiredData]) -> Self {
+        GetPublicDataParameters {
+            required_data: Some(required_data.to_vec()),
+            ..self
+        }
+    }
+
+    pub fn filter(self, filter: bool) -> Self {
+        GetPublicDataParameters {
+            filter: Some(filter),
+            ..self
+        }
+    }
+
+    /// Checks that the corners describe an area, which `get_public_data` does before calling the API.
+    pub fn validate(&self) -> Result<()> {
+        for (name, lat) in [("lat_ne", self.lat_ne), ("lat_sw", self.lat_sw)] {
+            if !(-90.0..=90.0).contains(&lat) {
+                return Err(NetatmoError::invalid_parameter(name, "must be between -90 and 90"));
+            }
+        }
+        for (name, lon) in [("lon_ne", self.lon_ne), ("lon_sw", self.lon_sw)] {
+            if !(-180.0..=180.0).contains(&lon) {
+                return Err(NetatmoError::invalid_parameter(name, "must be between -180 and 180"));
+            }
+        }
+        if self.lat_ne <= self.lat_sw {
+            return Err(NetatmoError::invalid_parameter("lat_ne", "must be north of lat_sw"));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+#[non_exhaustive]
+pub enum RequiredData {
+    #[default]
+    Temperature,
+    Humidity,
+    Pressure,
+    Rain,
+    Wind,
+}
+
+impl fmt::Display for RequiredData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            RequiredData::Temperature => "temperature",
+            RequiredData::Humidity => "humidity",
+            RequiredData::Pressure => "pressure",
+            RequiredData::Rain => "rain",
+            RequiredData::Wind => "wind",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for RequiredData {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "temperature" => Ok(RequiredData::Temperature),
+            "humidity" => Ok(RequiredData::Humidity),
+            "pressure" => Ok(RequiredData::Pressure),
+            "rain" => Ok(RequiredData::Rain),
+            "wind" => Ok(RequiredData::Wind),
+            _ => Err(NetatmoError::invalid_parameter(
+                "required_data",
+                &format!("unknown value '{}'", s),
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for RequiredData {
+    type Error = NetatmoError;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<RequiredData> for String {
+    fn from(data: RequiredData) -> Self {
+        data.to_string()
+    }
+}
+
+#[allow(clippy::implicit_hasher)]
+impl From<&GetPublicDataParameters> for HashMap<&'static str, String> {
+    fn from(p: &GetPublicDataParameters) -> HashMap<&'static str, String> {
+        let mut map = HashMap::default();
+        map.insert("lat_ne", p.lat_ne.to_string());
+        map.insert("lon_ne", p.lon_ne.to_string());
+        map.insert("lat_sw", p.lat_sw.to_string());
+        map.insert("lon_sw", p.lon_sw.to_string());
+        if let Some(required_data) = &p.required_data {
+            let required_data = required_data
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .as_slice()
+                .join(",");
+            map.insert("required_data", required_data);
+        }
+        if let Some(filter) = p.filter {
+            map.insert("filter", filter.to_string());
+        }
+
+        map
+    }
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/weather#getpublicdata
+pub async fn get_public_data(client: &NetatmoClient, parameters: &GetPublicDataParameters) -> Result<PublicData> {
+    parameters.validate()?;
+    let params: HashMap<&str, String> = parameters.into();
+    client
+        .call("get_public_data", "https://api.netatmo.com/api/getpublicdata", &params)
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::assert_round_trip;
+
+    mod get_public_data {
+        use super::*;
+
+        #[test]
+        fn parse_response() {
+            let json = r#"{
+                "status": "ok",
+                "time_server": 1556451492,
+                "body": [
+                  {
+                    "_id": "70:ee:50:00:00:01",
+                    "place": {
+                      "location": [
+                        2.3522,
+                        48.8566
+                      ],
+                      "timezone": "Europe/Paris",
+                      "country": "FR",
+                      "altitude": 35,
+                      "city": "Paris",
+                      "street": "Rue de Rivoli"
+                    },
+                    "mark": 10,
+                    "measures": {
+                      "02:00:00:00:00:01": {
+                        "res": {
+                          "1556451224": [
+                            13.8,
+                            53
+                          ]
+                        },
+                        "type": [
+                          "temperature",
+                          "humidity"
+                        ]
+                      },
+                      "70:ee:50:00:00:01": {
+                        "res": {
+                          "1556451224": [
+                            1019.3
+                          ]
+                        },
+                        "type": [
+                          "pressure"
+                        ]
+                      },
+                      "05:00:00:00:00:01": {
+                        "rain_60min": 0.2,
+                        "rain_24h": 3.1,
+                        "rain_live": 0,
+                        "rain_timeutc": 1556451224
+                      },
+                      "06:00:00:00:00:01": {
+                        "wind_strength": 3,
+                        "wind_angle": 200,
+                        "gust_strength": 8,
+                        "gust_angle": 190,
+                        "wind_timeutc": 1556451224
+                      }
+                    },
+                    "modules": [
+                      "02:00:00:00:00:01",
+                      "05:00:00:00:00:01",
+                      "06:00:00:00:00:01"
+                    ],
+                    "module_types": {
+                      "02:00:00:00:00:01": "NAModule1",
+                      "05:00:00:00:00:01": "NAModule3",
+                      "06:00:00:00:00:01": "NAModule2"
+                    }
+                  }
+                ]
+              }"#;
+
+            let public_data: std::result::Result<PublicData, _> = serde_json::from_str(json);
+
+            assert!(&public_data.is_ok());
+        }
+
+        #[test]
+        fn required_data_round_trip() {
+            assert_round_trip!(
+                RequiredData::Temperature,
+                RequiredData::Humidity,
+                RequiredData::Pressure,
+                RequiredData::Rain,
+                RequiredData::Wind,
+            );
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use super::{
+    ids::HomeId,
+    timestamp::{epoch, Epoch},
+};
+use crate::{client::NetatmoClient, errors::Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Scenarios {
+    pub status: String,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+    pub body: ScenariosBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct ScenariosBody {
+    pub home: ScenariosHome,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct ScenariosHome {
+    pub id: HomeId,
+    #[serde(default)]
+    pub scenarios: Vec<Scenario>,
+}
+
+/// A scene configured in the Home + Control app, e.g. `home`, `away`, `night` or `wake_up`, or a custom one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Scenario {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub name: Option<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub customizable: bool,
+    #[serde(default)]
+    pub editable: bool,
+    #[serde(default)]
+    pub deletable: bool,
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/control#getscenarios
+pub async fn get_scenarios(client: &NetatmoClient, home_id: &HomeId) -> Result<Scenarios> {
+    let mut params: HashMap<&str, String> = HashMap::default();
+    params.insert("home_id", home_id.to_string());
+
+    client
+        .call("get_scenarios", "https://api.netatmo.com/api/getscenarios", &params)
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod get_scenarios {
+        use super::*;
+
+        #[test]
+        fn parse_response() {
+            let json = r#"{
+                "body": {
+                  "home": {
+                    "id": "...",
+                    "scenarios": [
+                      {
+                        "type": "wake_up",
+                        "id": "wake_up",
+                        "category": "wake_up",
+                        "customizable": true,
+                        "editable": true,
+                        "deletable": false,
+                        "modules": []
+                      },
+                      {
+                        "type": "custom",
+                        "id": "5f...",
+                        "name": "Movie night",
+                        "customizable": true,
+                        "editable": true,
+                        "deletable": true
+                      }
+                    ]
+                  }
+                },
+                "status": "ok",
+                "time_server": 1689865700
+              }"#;
+
+            let scenarios: std::result::Result<Scenarios, _> = serde_json::from_str(json);
+
+            assert_eq!(scenarios.unwrap().body.home.scenarios.len(), 2);
+        }
+    }
+}
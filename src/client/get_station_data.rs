@@ -1,45 +1,98 @@
+use super::{
+    comfort::FeelLikeAlgorithm,
+    format::UnitSystem,
+    geo::Coordinates,
+    get_homecoachs_data::HealthIndex,
+    mac_address::MacAddress,
+    module_type::ModuleType,
+    timestamp::{epoch, Epoch, TimeZone},
+    units::{Pressure, PressureUnit, WindSpeed, WindUnit},
+};
 use crate::{client::NetatmoClient, errors::Result};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct StationData {
     pub body: StationDataBody,
     pub status: String,
     pub time_exec: f64,
-    pub time_server: u64,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct StationDataBody {
     pub devices: Vec<Device>,
     pub user: User,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Device {
     #[serde(rename = "_id")]
     pub id: String,
+    // Favorite stations of other users lack their setup and status details
+    #[serde(default)]
     pub co2_calibrating: bool,
-    pub date_setup: u64,
-    pub firmware: u64,
-    pub last_setup: u64,
-    pub last_status_store: u64,
-    pub last_upgrade: Option<u64>,
+    #[serde(default)]
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub date_setup: Epoch,
+    #[serde(default)]
+    pub firmware: i64,
+    #[serde(default)]
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub last_setup: Epoch,
+    #[serde(default)]
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub last_status_store: Epoch,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub last_upgrade: Option<Epoch>,
     pub module_name: Option<String>,
     pub reachable: bool,
     pub station_name: String,
     #[serde(rename = "type")]
-    pub type_info: String,
+    pub type_info: ModuleType,
+    #[serde(default)]
     pub wifi_status: f64,
     pub dashboard_data: DashboardData,
     pub data_type: Vec<String>,
     #[serde(default)]
     pub modules: Vec<Module>,
     pub place: Place,
+    /// Whether this is another user's public station the user marked as favorite, only returned with `get_favorites`
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl StationDataBody {
+    pub fn owned_devices(&self) -> impl Iterator<Item = &Device> {
+        self.devices.iter().filter(|x| !x.favorite)
+    }
+
+    pub fn favorite_devices(&self) -> impl Iterator<Item = &Device> {
+        self.devices.iter().filter(|x| x.favorite)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct DashboardData {
     #[serde(rename = "AbsolutePressure")]
     pub absolute_pressure: Option<f64>,
@@ -53,17 +106,234 @@ pub struct DashboardData {
     pub pressure: Option<f64>,
     #[serde(rename = "Temperature")]
     pub temperature: Option<f64>,
-    pub health_idx: Option<u8>,
-    pub date_max_temp: Option<u64>,
-    pub date_min_temp: Option<u64>,
+    pub health_idx: Option<HealthIndex>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_max_temp: Option<Epoch>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_min_temp: Option<Epoch>,
     pub max_temp: Option<f64>,
     pub min_temp: Option<f64>,
     pub pressure_trend: Option<String>,
     pub temp_trend: Option<String>,
-    pub time_utc: Option<u64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub time_utc: Option<Epoch>,
+    #[serde(rename = "Rain")]
+    pub rain: Option<f64>,
+    pub sum_rain_1: Option<f64>,
+    pub sum_rain_24: Option<f64>,
+    #[serde(rename = "WindStrength")]
+    pub wind_strength: Option<u64>,
+    #[serde(rename = "WindAngle")]
+    pub wind_angle: Option<u64>,
+    #[serde(rename = "GustStrength")]
+    pub gust_strength: Option<u64>,
+    #[serde(rename = "GustAngle")]
+    pub gust_angle: Option<u64>,
+    pub max_wind_str: Option<u64>,
+    pub max_wind_angle: Option<u64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_max_wind_str: Option<Epoch>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// The measurements of a weather module, typed by the kind of module that reported them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum WeatherDashboard {
+    /// NAMain
+    Main(MainDashboard),
+    /// NAModule1
+    Outdoor(OutdoorDashboard),
+    /// NAModule2
+    Wind(WindDashboard),
+    /// NAModule3
+    Rain(RainDashboard),
+    /// NAModule4
+    AdditionalIndoor(AdditionalIndoorDashboard),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct MainDashboard {
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_utc: Epoch,
+    pub temperature: f64,
+    pub humidity: u64,
+    pub co2: u64,
+    pub noise: u64,
+    pub pressure: Pressure,
+    pub absolute_pressure: Pressure,
+    pub min_temp: Option<f64>,
+    pub max_temp: Option<f64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_min_temp: Option<Epoch>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_max_temp: Option<Epoch>,
+    pub temp_trend: Option<String>,
+    pub pressure_trend: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct OutdoorDashboard {
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_utc: Epoch,
+    pub temperature: f64,
+    pub humidity: u64,
+    pub min_temp: Option<f64>,
+    pub max_temp: Option<f64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_min_temp: Option<Epoch>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_max_temp: Option<Epoch>,
+    pub temp_trend: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct WindDashboard {
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_utc: Epoch,
+    pub wind_strength: WindSpeed,
+    pub wind_angle: u64,
+    pub gust_strength: WindSpeed,
+    pub gust_angle: u64,
+    pub max_wind_str: Option<WindSpeed>,
+    pub max_wind_angle: Option<u64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_max_wind_str: Option<Epoch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct RainDashboard {
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_utc: Epoch,
+    pub rain: f64,
+    pub sum_rain_1: f64,
+    pub sum_rain_24: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct AdditionalIndoorDashboard {
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_utc: Epoch,
+    pub temperature: f64,
+    pub humidity: u64,
+    pub co2: u64,
+    pub min_temp: Option<f64>,
+    pub max_temp: Option<f64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_min_temp: Option<Epoch>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub date_max_temp: Option<Epoch>,
+    pub temp_trend: Option<String>,
+}
+
+impl DashboardData {
+    /// Interprets the measurements for the given module type; `None` if the type is unknown or a measurement the
+    /// type always reports is missing, e.g. because the module is unreachable.
+    pub fn typed(&self, module_type: &ModuleType) -> Option<WeatherDashboard> {
+        let d = self.clone();
+        let dashboard = match module_type {
+            ModuleType::NAMain => WeatherDashboard::Main(MainDashboard {
+                time_utc: d.time_utc?,
+                temperature: d.temperature?,
+                humidity: d.humidity?,
+                co2: d.co2?,
+                noise: d.noise?,
+                pressure: Pressure::from_mbar(d.pressure?),
+                absolute_pressure: Pressure::from_mbar(d.absolute_pressure?),
+                min_temp: d.min_temp,
+                max_temp: d.max_temp,
+                date_min_temp: d.date_min_temp,
+                date_max_temp: d.date_max_temp,
+                temp_trend: d.temp_trend,
+                pressure_trend: d.pressure_trend,
+            }),
+            ModuleType::NAModule1 => WeatherDashboard::Outdoor(OutdoorDashboard {
+                time_utc: d.time_utc?,
+                temperature: d.temperature?,
+                humidity: d.humidity?,
+                min_temp: d.min_temp,
+                max_temp: d.max_temp,
+                date_min_temp: d.date_min_temp,
+                date_max_temp: d.date_max_temp,
+                temp_trend: d.temp_trend,
+            }),
+            ModuleType::NAModule2 => WeatherDashboard::Wind(WindDashboard {
+                time_utc: d.time_utc?,
+                wind_strength: WindSpeed::from_kph(d.wind_strength? as f64),
+                wind_angle: d.wind_angle?,
+                gust_strength: WindSpeed::from_kph(d.gust_strength? as f64),
+                gust_angle: d.gust_angle?,
+                max_wind_str: d.max_wind_str.map(|x| WindSpeed::from_kph(x as f64)),
+                max_wind_angle: d.max_wind_angle,
+                date_max_wind_str: d.date_max_wind_str,
+            }),
+            ModuleType::NAModule3 => WeatherDashboard::Rain(RainDashboard {
+                time_utc: d.time_utc?,
+                rain: d.rain?,
+                sum_rain_1: d.sum_rain_1?,
+                sum_rain_24: d.sum_rain_24?,
+            }),
+            ModuleType::NAModule4 => WeatherDashboard::AdditionalIndoor(AdditionalIndoorDashboard {
+                time_utc: d.time_utc?,
+                temperature: d.temperature?,
+                humidity: d.humidity?,
+                co2: d.co2?,
+                min_temp: d.min_temp,
+                max_temp: d.max_temp,
+                date_min_temp: d.date_min_temp,
+                date_max_temp: d.date_max_temp,
+                temp_trend: d.temp_trend,
+            }),
+            _ => return None,
+        };
+
+        Some(dashboard)
+    }
+}
+
+impl Device {
+    pub fn dashboard(&self) -> Option<WeatherDashboard> {
+        self.dashboard_data.typed(&self.type_info)
+    }
+}
+
+impl Module {
+    pub fn dashboard(&self) -> Option<WeatherDashboard> {
+        self.dashboard_data.typed(&self.type_info)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Module {
     #[serde(rename = "_id")]
     pub id: String,
@@ -71,67 +341,114 @@ pub struct Module {
     pub battery_vp: u64,
     pub dashboard_data: DashboardData,
     pub data_type: Vec<String>,
-    pub firmware: u64,
-    pub last_message: u64,
-    pub last_seen: u64,
-    pub last_setup: u64,
+    pub firmware: i64,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub last_message: Epoch,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub last_seen: Epoch,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub last_setup: Epoch,
     pub module_name: String,
     pub reachable: bool,
     pub rf_status: u64,
     #[serde(rename = "type")]
-    pub type_info: String,
+    pub type_info: ModuleType,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Place {
     pub altitude: u64,
     pub city: String,
     pub country: String,
-    pub location: Vec<f64>,
-    pub timezone: String,
+    pub location: Coordinates,
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub timezone: TimeZone,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct User {
     pub administrative: Administrative,
     pub mail: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct Administrative {
     pub country: Option<String>,
-    pub feel_like_algo: u64,
+    pub feel_like_algo: FeelLikeAlgorithm,
     pub lang: String,
-    pub pressureunit: Option<u64>,
+    pub pressureunit: Option<PressureUnit>,
     pub reg_locale: String,
-    pub unit: u64,
-    pub windunit: u64,
+    pub unit: UnitSystem,
+    pub windunit: WindUnit,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+pub struct GetStationDataParameters {
+    device_id: Option<MacAddress>,
+    get_favorites: Option<bool>,
+}
+
+impl GetStationDataParameters {
+    pub fn new() -> Self {
+        GetStationDataParameters::default()
+    }
+
+    pub fn device_id(self, device_id: &MacAddress) -> Self {
+        GetStationDataParameters {
+            device_id: Some(device_id.clone()),
+            ..self
+        }
+    }
+
+    pub fn get_favorites(self, get_favorites: bool) -> Self {
+        GetStationDataParameters {
+            get_favorites: Some(get_favorites),
+            ..self
+        }
+    }
+}
+
+#[allow(clippy::implicit_hasher)]
+impl From<&GetStationDataParameters> for HashMap<&'static str, String> {
+    fn from(p: &GetStationDataParameters) -> HashMap<&'static str, String> {
+        let mut map = HashMap::default();
+        if let Some(device_id) = &p.device_id {
+            map.insert("device_id", device_id.to_string());
+        }
+        if let Some(get_favorites) = p.get_favorites {
+            map.insert("get_favorites", get_favorites.to_string());
+        }
+
+        map
+    }
 }
 
-pub async fn get_station_data(client: &NetatmoClient, device_id: &str) -> Result<StationData> {
-    let mut params: HashMap<String, String> = HashMap::default();
-    params.insert("device_id".to_string(), device_id.to_string());
+// cf. https://dev.netatmo.com/apidocumentation/weather#getstationsdata
+pub async fn get_stations_data(client: &NetatmoClient, parameters: &GetStationDataParameters) -> Result<StationData> {
+    let params: HashMap<&str, String> = parameters.into();
 
     client
         .call(
             "get_station_data",
             "https://api.netatmo.com/api/getstationsdata",
-            &mut params,
+            &params,
         )
         .await
 }
 
-pub async fn get_homecoachs_data(client: &NetatmoClient, device_id: &str) -> Result<StationData> {
-    let mut params: HashMap<String, String> = HashMap::default();
-    params.insert("device_id".to_string(), device_id.to_string());
-
-    client
-        .call(
-            "get_homecoachs_data",
-            "https://api.netatmo.com/api/gethomecoachsdata",
-            &mut params,
-        )
-        .await
+pub async fn get_station_data(client: &NetatmoClient, device_id: &MacAddress) -> Result<StationData> {
+    get_stations_data(client, &GetStationDataParameters::new().device_id(device_id)).await
 }
 
 #[cfg(test)]
@@ -215,7 +532,7 @@ mod test {
             82.5057837,
             -62.5575262
           ],
-          "timezone": "EDT"
+          "timezone": "America/Toronto"
         },
         "reachable": true,
         "station_name": "Home",
@@ -243,79 +560,46 @@ mod test {
             let station_data: std::result::Result<StationData, _> = serde_json::from_str(json);
 
             assert!(&station_data.is_ok());
-        }
-    }
 
-    mod get_homecoach_data {
-        use super::*;
+            let device = &station_data.unwrap().body.devices[0];
+            assert!(device.extra["cipher_id"].as_str().unwrap().starts_with("enc:"));
+            assert!(matches!(
+                device.dashboard(),
+                Some(WeatherDashboard::Main(MainDashboard { noise: 40, .. }))
+            ));
+            assert!(matches!(
+                device.modules[0].dashboard(),
+                Some(WeatherDashboard::Outdoor(_))
+            ));
+        }
 
         #[test]
-        fn parse_response() {
+        fn parse_favorite_station() {
             let json = r#"{
-  "body": {
-    "devices": [
-      {
-        "_id": "12:34:56:78:90:AB",
-        "co2_calibrating": false,
-        "dashboard_data": {
-          "AbsolutePressure": 1013.3,
-          "CO2": 455,
-          "Humidity": 43,
-          "Noise": 40,
-          "Pressure": 1019.3,
-          "Temperature": 20.3,
-          "health_idx": 1,
-          "time_utc": 1556451224
-        },
-        "data_type": [
-          "Temperature",
-          "CO2",
-          "Humidity",
-          "Noise",
-          "Pressure",
-          "health_idx"
-        ],
-        "date_setup": 1556295333,
-        "firmware": 140,
-        "last_setup": 1556295333,
-        "last_status_store": 1556451233,
-        "last_upgrade": 1556295520,
-        "place": {
-          "altitude": 50,
-          "city": "Alert",
-          "country": "CAN",
-          "location": [
-            82.5057837,
-            -62.5575262
-          ],
-          "timezone": "EDT"
-        },
-        "reachable": true,
-        "station_name": "Home",
-        "type": "NAMain",
-        "wifi_status": 50
-      }
-    ],
-    "user": {
-      "administrative": {
-        "feel_like_algo": 0,
-        "lang": "en-US",
-        "pressureunit": 0,
-        "reg_locale": "en-US",
-        "unit": 0,
-        "windunit": 0
-      },
-      "mail": "lukas at my_domain"
-    }
+  "_id": "70:ee:50:00:00:02",
+  "station_name": "Neighbour",
+  "type": "NAMain",
+  "reachable": true,
+  "favorite": true,
+  "read_only": true,
+  "dashboard_data": {
+    "Temperature": 19.1,
+    "Humidity": 51,
+    "time_utc": 1556451224
   },
-  "status": "ok",
-  "time_exec": 0.13046002388,
-  "time_server": 1556451492
+  "data_type": ["Temperature", "Humidity"],
+  "place": {
+    "altitude": 30,
+    "city": "Alert",
+    "country": "CAN",
+    "location": [82.50, -62.55],
+    "timezone": "America/Toronto"
+  }
 }"#;
 
-            let homecoachs_data: ::std::result::Result<StationData, _> = serde_json::from_str(json);
+            let device: std::result::Result<Device, _> = serde_json::from_str(json);
 
-            assert!(&homecoachs_data.is_ok());
+            assert!(device.unwrap().favorite);
         }
     }
 }
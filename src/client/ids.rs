@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::{borrow::Borrow, fmt};
+
+macro_rules! id_type {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(id: &str) -> Self {
+                $name(id.to_string())
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                $name::new(id)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+id_type!(
+    /// The id of a home, as returned by `homesdata`.
+    HomeId
+);
+
+id_type!(
+    /// The id of a room within a home.
+    RoomId
+);
+
+id_type!(
+    /// The id of a module of a home, usually its MAC address, e.g. of a valve, a camera or the relay bridging it.
+    ///
+    /// The home APIs report and accept it as is, while the weather APIs take a validated [`MacAddress`]; convert with
+    /// `MacAddress::try_from(&module_id)`, which fails for modules not identified by a MAC address.
+    ///
+    /// [`MacAddress`]: super::mac_address::MacAddress
+    ModuleId
+);
+
+id_type!(
+    /// The id of a person known to the cameras of a home, as listed by `homesdata`.
+    PersonId
+);
+
+id_type!(
+    /// The id of a heating schedule of a home.
+    ScheduleId
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod ids {
+        use super::*;
+
+        #[test]
+        fn transparent() {
+            let id: HomeId = serde_json::from_str(r#""5e1d9e6c2c4b3e0a5c8b4567""#).unwrap();
+
+            assert_eq!(id, "5e1d9e6c2c4b3e0a5c8b4567");
+            assert_eq!(serde_json::to_string(&id).unwrap(), r#""5e1d9e6c2c4b3e0a5c8b4567""#);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use super::ids::ModuleId;
+use crate::errors::NetatmoError;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// The MAC address identifying a device or module, e.g. `70:ee:50:00:00:01`.
+///
+/// Parsing accepts upper case and `-` separators and normalizes them to the lower case, `:` separated form the API
+/// uses, so typos are caught before a call fails with an invalid device id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+pub struct MacAddress(String);
+
+impl MacAddress {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for MacAddress {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let octets: Vec<&str> = s.split([':', '-']).collect();
+        let valid = octets.len() == 6
+            && octets
+                .iter()
+                .all(|x| x.len() == 2 && x.chars().all(|c| c.is_ascii_hexdigit()));
+        if !valid {
+            return Err(NetatmoError::InvalidMacAddress { value: s.to_string() });
+        }
+
+        Ok(MacAddress(octets.join(":").to_ascii_lowercase()))
+    }
+}
+
+impl TryFrom<String> for MacAddress {
+    type Error = NetatmoError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<&str> for MacAddress {
+    type Error = NetatmoError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<&ModuleId> for MacAddress {
+    type Error = NetatmoError;
+
+    fn try_from(id: &ModuleId) -> Result<Self, Self::Error> {
+        id.as_str().parse()
+    }
+}
+
+impl From<MacAddress> for ModuleId {
+    fn from(mac: MacAddress) -> Self {
+        ModuleId::from(mac.0)
+    }
+}
+
+impl From<MacAddress> for String {
+    fn from(mac: MacAddress) -> Self {
+        mac.0
+    }
+}
+
+impl AsRef<str> for MacAddress {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod mac_address {
+        use super::*;
+
+        #[test]
+        fn normalize() {
+            let mac: MacAddress = "70-EE-50-0A:bc:01".parse().unwrap();
+
+            assert_eq!(mac.to_string(), "70:ee:50:0a:bc:01");
+        }
+
+        #[test]
+        fn reject_invalid() {
+            for s in [
+                "",
+                "70:ee:50:0a:bc",
+                "70:ee:50:0a:bc:0g",
+                "70:ee:50:0a:bc:012",
+                "70ee500abc01",
+            ] {
+                assert_eq!(
+                    s.parse::<MacAddress>(),
+                    Err(NetatmoError::InvalidMacAddress { value: s.to_string() })
+                );
+            }
+        }
+
+        #[test]
+        fn from_module_id() {
+            let mac = MacAddress::try_from(&ModuleId::new("70:EE:50:0a:bc:01")).unwrap();
+
+            assert_eq!(ModuleId::from(mac), "70:ee:50:0a:bc:01");
+            assert!(MacAddress::try_from(&ModuleId::new("1234567890")).is_err());
+        }
+    }
+}
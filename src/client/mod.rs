@@ -1,30 +1,113 @@
 use self::{
+    add_webhook::{add_webhook, AddWebhookResponse},
+    camera::{cached_camera_url, camera_snapshot, resolve_camera_url, CameraUrl},
+    contactor::set_contactor,
+    drop_webhook::{drop_webhook, DropWebhookParameters, DropWebhookResponse},
+    event_media::{download_event_snapshot, download_event_vignette},
+    get_camera_picture::{get_camera_picture, PictureCache},
+    get_events::{events_since, get_events, Event, Events, GetEventsParameters},
     get_home_status::{get_home_status, GetHomeStatusParameters},
+    get_homecoachs_data::{get_homecoachs_data, HomeCoachData},
     get_homes_data::{get_homes_data, GetHomesDataParameters},
-    get_measure::{get_measure, GetMeasureParameters},
-    get_station_data::{get_homecoachs_data, get_station_data},
-    set_room_thermpoint::{set_room_thermpoint, SetRoomThermpointParameters, SetRoomThermpointResponse},
+    get_last_event_of::{get_last_event_of, GetLastEventOfParameters, LastEventOf},
+    get_measure::{get_measure, get_measure_range, get_measure_series, GetMeasureParameters},
+    get_public_data::{get_public_data, GetPublicDataParameters, PublicData},
+    get_scenarios::{get_scenarios, Scenarios},
+    get_station_data::{get_station_data, get_stations_data, GetStationDataParameters},
+    presence::get_persons_at_home,
+    room_energy::room_energy,
+    set_persons_away::{set_persons_away, SetPersonsAwayResponse},
+    set_persons_home::{set_persons_home, SetPersonsHomeResponse},
+    set_room_thermpoint::{
+        room_frost_guard, room_off, set_room_thermpoint, set_rooms_thermpoint, Mode, SetRoomThermpointParameters,
+        SetRoomThermpointResponse,
+    },
+    set_state::{
+        launch_scenario, set_brightness, set_camera_monitoring, set_floodlight, set_light, set_plug, set_shutter,
+        set_state, set_states, FloodlightMode, ModuleState, Monitoring, SetStateError, SetStateParameters,
+        SetStateResponse, ShutterPosition,
+    },
+    sync_home_schedule::{sync_home_schedule, SyncHomeScheduleParameters, SyncHomeScheduleResponse},
 };
 use crate::errors::{NetatmoError, Result};
+use bytes::Bytes;
+use futures::Stream;
 use get_home_status::HomeStatus;
-use get_homes_data::HomesData;
+use get_homes_data::{Home, HomesData, Person};
 use get_measure::Measure;
 use get_station_data::StationData;
-use log::trace;
+use ids::{HomeId, ModuleId, PersonId, RoomId};
+use log::{debug, log_enabled, trace, Level};
+use mac_address::MacAddress;
+use module_status::CameraStatus;
 use reqwest::{Client, Response, StatusCode};
 use serde::de::DeserializeOwned;
-use serde::Deserialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, future::Future, sync::Mutex, time::Duration};
+use time_series::TimeSeries;
 
+pub mod add_webhook;
+pub mod air_quality;
+pub mod battery;
+pub mod camera;
+pub mod comfort;
+pub mod contactor;
+#[cfg(feature = "chrono")]
+pub mod datetime;
+pub mod doorbell;
+pub mod drop_webhook;
+pub mod event_filter;
+pub mod event_media;
+pub mod event_sequencer;
+pub mod firmware;
+pub mod fleet_report;
+pub mod format;
+pub mod geo;
+pub mod get_camera_picture;
+pub mod get_events;
 pub mod get_home_status;
+pub mod get_homecoachs_data;
 pub mod get_homes_data;
+pub mod get_last_event_of;
 pub mod get_measure;
+pub mod get_next_events;
+pub mod get_public_data;
+pub mod get_scenarios;
 pub mod get_station_data;
+pub mod ids;
+pub mod mac_address;
+pub mod module_errors;
+pub mod module_status;
+pub mod module_type;
+pub mod presence;
+pub mod public_aggregate;
+pub mod rain;
+pub mod reachability;
+pub mod remote;
+pub mod room_energy;
+pub mod room_type;
+pub mod sanity;
+pub mod scope;
+pub mod set_persons_away;
+pub mod set_persons_home;
 pub mod set_room_thermpoint;
+pub mod set_state;
+pub mod signal;
+pub mod smoke_detector;
+pub mod sync_home_schedule;
+#[cfg(test)]
+pub(crate) mod test_util;
+pub mod time_series;
+pub mod timestamp;
+#[cfg(feature = "chrono-tz")]
+pub mod timezone;
+pub mod units;
 
 pub struct NetatmoClient {
     token: String,
     http: Client,
+    camera_urls: Mutex<HashMap<ModuleId, CameraUrl>>,
+    picture_cache: Option<Mutex<PictureCache>>,
 }
 
 impl NetatmoClient {
@@ -32,6 +115,17 @@ impl NetatmoClient {
         Self {
             token: access_token.to_string(),
             http: Client::new(),
+            camera_urls: Mutex::new(HashMap::new()),
+            picture_cache: None,
+        }
+    }
+
+    /// Keeps up to `capacity` pictures fetched via `get_camera_picture` in memory, so repeated downloads of the
+    /// same image don't count against the rate limit.
+    pub fn with_picture_cache(self, capacity: usize) -> Self {
+        Self {
+            picture_cache: Some(Mutex::new(PictureCache::new(capacity))),
+            ..self
         }
     }
 
@@ -39,41 +133,138 @@ impl NetatmoClient {
         &self.token
     }
 
-    pub async fn call<T>(&self, name: &str, url: &str, params: &mut HashMap<String, String>) -> Result<T>
+    /// Posts `params` as form to `url`, together with the access token.
+    pub async fn call<T>(&self, name: &str, url: &str, params: &HashMap<&str, String>) -> Result<T>
     where
         T: DeserializeOwned,
     {
-        params.insert("access_token".to_string(), self.token.clone());
-        api_call(name, &self.http, url, params).await
+        api_call(name, &self.http, url, &self.token, params).await
+    }
+
+    pub async fn call_binary(&self, name: &str, url: &str, params: &HashMap<&str, String>) -> Result<Bytes> {
+        api_call_binary(name, &self.http, url, &self.token, params).await
     }
+
+    pub async fn call_json<T, B>(&self, name: &str, url: &str, body: &B) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        api_call_json(name, &self.http, url, &self.token, body).await
+    }
+}
+
+#[derive(Serialize)]
+struct AuthenticatedForm<'a> {
+    access_token: &'a str,
+    #[serde(flatten)]
+    params: &'a HashMap<&'a str, String>,
+}
+
+async fn api_call<T>(name: &str, http: &Client, url: &str, token: &str, params: &HashMap<&str, String>) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let res = http
+        .post(url)
+        .form(&AuthenticatedForm {
+            access_token: token,
+            params,
+        })
+        .send()
+        .await
+        .map_err(|_| NetatmoError::FailedToSendRequest)?;
+
+    read_response(name, res).await
 }
 
-async fn api_call<T>(name: &str, http: &Client, url: &str, params: &HashMap<String, String>) -> Result<T>
+async fn api_call_binary(
+    name: &str,
+    http: &Client,
+    url: &str,
+    token: &str,
+    params: &HashMap<&str, String>,
+) -> Result<Bytes> {
+    let res = http
+        .post(url)
+        .form(&AuthenticatedForm {
+            access_token: token,
+            params,
+        })
+        .send()
+        .await
+        .map_err(|_| NetatmoError::FailedToSendRequest)?;
+
+    let res = general_err_handler(res, name.to_string(), StatusCode::OK).await?;
+
+    let status = res.status();
+    let body = res.bytes().await.map_err(|_| NetatmoError::FailedToReadResponse)?;
+    trace!("Sucessful ({:?}) binary response of {} bytes", status, body.len());
+    Ok(body)
+}
+
+async fn api_call_json<T, B>(name: &str, http: &Client, url: &str, token: &str, body: &B) -> Result<T>
 where
     T: DeserializeOwned,
+    B: Serialize + ?Sized,
 {
     let res = http
         .post(url)
-        .form(&params)
+        .bearer_auth(token)
+        .json(body)
         .send()
         .await
         .map_err(|_| NetatmoError::FailedToSendRequest)?;
 
+    read_response(name, res).await
+}
+
+async fn read_response<T>(name: &str, res: Response) -> Result<T>
+where
+    T: DeserializeOwned,
+{
     let res = general_err_handler(res, name.to_string(), StatusCode::OK).await?;
 
     let status = res.status();
-    let body = res.text().await.map_err(|_| NetatmoError::FailedToReadResponse)?;
-    trace!("Sucessful ({:?}) repsone: '{}'", status, body);
-    serde_json::from_str::<T>(&body).map_err(|_| NetatmoError::JsonDeserializationFailed)
+    let body = res.bytes().await.map_err(|_| NetatmoError::FailedToReadResponse)?;
+    if log_enabled!(Level::Trace) {
+        trace!("Sucessful ({:?}) repsone: '{}'", status, String::from_utf8_lossy(&body));
+    }
+    serde_json::from_slice::<T>(&body).map_err(|_| NetatmoError::JsonDeserializationFailed)
+}
+
+const RATE_LIMIT_RETRIES: u32 = 3;
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Retries `f` with an exponential backoff as long as Netatmo reports the rate limit as exceeded.
+pub(crate) async fn retry_on_rate_limit<T, F, Fut>(mut f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut backoff = RATE_LIMIT_BACKOFF;
+    for _ in 0..RATE_LIMIT_RETRIES {
+        match f().await {
+            Err(e) if e.is_rate_limited() => {
+                debug!("Rate limit reached, retrying in {:?}", backoff);
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            res => return res,
+        }
+    }
+    f().await
 }
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 struct ApiError {
     #[serde(rename = "error")]
     details: ApiErrorDetails,
 }
 
 #[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 struct ApiErrorDetails {
     code: isize,
     message: String,
@@ -118,22 +309,307 @@ impl NetatmoClient {
         get_home_status(self, parameters).await
     }
 
-    pub async fn get_station_data(&self, device_id: &str) -> Result<StationData> {
+    pub async fn get_events(&self, parameters: &GetEventsParameters) -> Result<Events> {
+        get_events(self, parameters).await
+    }
+
+    pub fn events_since(&self, home_id: &HomeId, since: i64) -> impl Stream<Item = Result<Event>> + '_ {
+        events_since(self, home_id, since)
+    }
+
+    pub async fn get_last_event_of(&self, parameters: &GetLastEventOfParameters) -> Result<LastEventOf> {
+        get_last_event_of(self, parameters).await
+    }
+
+    pub async fn get_camera_picture(&self, image_id: &str, key: &str) -> Result<Bytes> {
+        get_camera_picture(self, image_id, key).await
+    }
+
+    pub async fn resolve_camera_url(&self, vpn_url: &str, is_local: bool) -> CameraUrl {
+        resolve_camera_url(self, vpn_url, is_local).await
+    }
+
+    pub async fn download_event_snapshot(&self, event: &Event) -> Result<Option<Bytes>> {
+        download_event_snapshot(self, event).await
+    }
+
+    pub async fn download_event_vignette(&self, event: &Event) -> Result<Option<Bytes>> {
+        download_event_vignette(self, event).await
+    }
+
+    pub async fn camera_url(&self, module: &CameraStatus) -> Option<CameraUrl> {
+        cached_camera_url(self, module).await
+    }
+
+    pub async fn camera_snapshot(&self, module: &CameraStatus) -> Result<Bytes> {
+        camera_snapshot(self, module).await
+    }
+
+    pub async fn persons_at_home(&self, home_id: &HomeId) -> Result<Vec<Person>> {
+        get_persons_at_home(self, home_id).await
+    }
+
+    pub async fn get_stations_data(&self, parameters: &GetStationDataParameters) -> Result<StationData> {
+        get_stations_data(self, parameters).await
+    }
+
+    #[deprecated(note = "use `get_stations_data`, which allows omitting the device id")]
+    pub async fn get_station_data(&self, device_id: &MacAddress) -> Result<StationData> {
         get_station_data(self, device_id).await
     }
 
-    pub async fn get_homecoachs_data(&self, device_id: &str) -> Result<StationData> {
+    pub async fn get_homecoachs_data(&self, device_id: &MacAddress) -> Result<HomeCoachData> {
         get_homecoachs_data(self, device_id).await
     }
 
+    pub async fn get_public_data(&self, parameters: &GetPublicDataParameters) -> Result<PublicData> {
+        get_public_data(self, parameters).await
+    }
+
     pub async fn get_measure(&self, parameters: &GetMeasureParameters) -> Result<Measure> {
         get_measure(self, parameters).await
     }
 
+    pub async fn get_measure_range(
+        &self,
+        parameters: &GetMeasureParameters,
+        start: i64,
+        end: i64,
+    ) -> Result<TimeSeries> {
+        get_measure_range(self, parameters, start, end).await
+    }
+
+    pub async fn get_measure_series(&self, parameters: &GetMeasureParameters) -> Result<TimeSeries> {
+        get_measure_series(self, parameters).await
+    }
+
     pub async fn set_room_thermpoint(
         &self,
         parameters: &SetRoomThermpointParameters,
     ) -> Result<SetRoomThermpointResponse> {
         set_room_thermpoint(self, parameters).await
     }
+
+    pub async fn room_off(&self, home_id: &HomeId, room_id: &RoomId) -> Result<SetRoomThermpointResponse> {
+        room_off(self, home_id, room_id).await
+    }
+
+    pub async fn room_frost_guard(&self, home_id: &HomeId, room_id: &RoomId) -> Result<SetRoomThermpointResponse> {
+        room_frost_guard(self, home_id, room_id).await
+    }
+
+    pub async fn set_rooms_thermpoint(
+        &self,
+        home_id: &HomeId,
+        rooms: &[(RoomId, f32)],
+        mode: Mode,
+    ) -> Vec<(RoomId, Result<SetRoomThermpointResponse>)> {
+        set_rooms_thermpoint(self, home_id, rooms, mode).await
+    }
+
+    pub async fn set_persons_away(
+        &self,
+        home_id: &HomeId,
+        person_id: Option<&PersonId>,
+    ) -> Result<SetPersonsAwayResponse> {
+        set_persons_away(self, home_id, person_id).await
+    }
+
+    pub async fn set_persons_home(&self, home_id: &HomeId, person_ids: &[PersonId]) -> Result<SetPersonsHomeResponse> {
+        set_persons_home(self, home_id, person_ids).await
+    }
+
+    pub async fn add_webhook(&self, callback_url: &str) -> Result<AddWebhookResponse> {
+        add_webhook(self, callback_url).await
+    }
+
+    pub async fn drop_webhook(&self, parameters: &DropWebhookParameters) -> Result<DropWebhookResponse> {
+        drop_webhook(self, parameters).await
+    }
+
+    pub async fn set_state(&self, parameters: &SetStateParameters) -> Result<SetStateResponse> {
+        set_state(self, parameters).await
+    }
+
+    pub async fn set_states(
+        &self,
+        home_id: &HomeId,
+        modules: &[ModuleState],
+    ) -> Result<HashMap<ModuleId, std::result::Result<(), SetStateError>>> {
+        set_states(self, home_id, modules).await
+    }
+
+    pub async fn set_camera_monitoring(
+        &self,
+        home_id: &HomeId,
+        module_id: &ModuleId,
+        monitoring: Monitoring,
+    ) -> Result<SetStateResponse> {
+        set_camera_monitoring(self, home_id, module_id, monitoring).await
+    }
+
+    pub async fn set_floodlight(
+        &self,
+        home_id: &HomeId,
+        module_id: &ModuleId,
+        floodlight: FloodlightMode,
+    ) -> Result<SetStateResponse> {
+        set_floodlight(self, home_id, module_id, floodlight).await
+    }
+
+    pub async fn set_plug(
+        &self,
+        home_id: &HomeId,
+        module_id: &ModuleId,
+        bridge: &ModuleId,
+        on: bool,
+    ) -> Result<SetStateResponse> {
+        set_plug(self, home_id, module_id, bridge, on).await
+    }
+
+    pub async fn set_shutter(
+        &self,
+        home_id: &HomeId,
+        module_id: &ModuleId,
+        bridge: &ModuleId,
+        position: ShutterPosition,
+    ) -> Result<SetStateResponse> {
+        set_shutter(self, home_id, module_id, bridge, position).await
+    }
+
+    pub async fn set_light(
+        &self,
+        home_id: &HomeId,
+        module_id: &ModuleId,
+        bridge: &ModuleId,
+        on: bool,
+    ) -> Result<SetStateResponse> {
+        set_light(self, home_id, module_id, bridge, on).await
+    }
+
+    pub async fn set_brightness(
+        &self,
+        home_id: &HomeId,
+        module_id: &ModuleId,
+        bridge: &ModuleId,
+        brightness: u8,
+    ) -> Result<SetStateResponse> {
+        set_brightness(self, home_id, module_id, bridge, brightness).await
+    }
+
+    pub async fn room_energy(&self, home: &Home, start: i64, end: i64) -> Result<HashMap<RoomId, f64>> {
+        room_energy(self, home, start, end).await
+    }
+
+    pub async fn set_contactor(
+        &self,
+        home_id: &HomeId,
+        module_id: &ModuleId,
+        bridge: &ModuleId,
+        on: bool,
+    ) -> Result<()> {
+        set_contactor(self, home_id, module_id, bridge, on).await
+    }
+
+    pub async fn get_scenarios(&self, home_id: &HomeId) -> Result<Scenarios> {
+        get_scenarios(self, home_id).await
+    }
+
+    pub async fn launch_scenario(
+        &self,
+        home_id: &HomeId,
+        bridge_id: &ModuleId,
+        scenario_id: &str,
+    ) -> Result<SetStateResponse> {
+        launch_scenario(self, home_id, bridge_id, scenario_id).await
+    }
+
+    pub async fn sync_home_schedule(
+        &self,
+        parameters: &SyncHomeScheduleParameters,
+    ) -> Result<SyncHomeScheduleResponse> {
+        sync_home_schedule(self, parameters).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod authenticated_form {
+        use super::*;
+
+        #[test]
+        fn sends_token_with_params() {
+            let params = HashMap::from([("home_id", "1234".to_string())]);
+            let request = Client::new()
+                .post("https://api.netatmo.com/api/homestatus")
+                .form(&AuthenticatedForm {
+                    access_token: "token",
+                    params: &params,
+                })
+                .build()
+                .unwrap();
+
+            let body = request.body().and_then(|x| x.as_bytes()).unwrap();
+            assert_eq!(body, b"access_token=token&home_id=1234");
+            assert!(request.headers().get("authorization").is_none());
+        }
+    }
+
+    mod retry_on_rate_limit {
+        use super::*;
+        use std::cell::Cell;
+
+        fn rate_limited() -> NetatmoError {
+            NetatmoError::ApiCallFailed {
+                name: "get_events".to_string(),
+                code: 26,
+                msg: "User usage reached".to_string(),
+            }
+        }
+
+        /// The result after `failures` rate limited calls, the number of calls and the time spent backing off.
+        async fn retry(failures: usize, error: NetatmoError) -> (Result<()>, usize, Duration) {
+            let calls = Cell::new(0);
+            let start = tokio::time::Instant::now();
+
+            let result = retry_on_rate_limit(|| {
+                calls.set(calls.get() + 1);
+                let result = if calls.get() > failures {
+                    Ok(())
+                } else {
+                    Err(error.clone())
+                };
+                async move { result }
+            })
+            .await;
+
+            (result, calls.get(), start.elapsed())
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn retries_with_backoff() {
+            assert_eq!(
+                retry(2, rate_limited()).await,
+                (Ok(()), 3, Duration::from_secs(10 + 20))
+            );
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn gives_up_after_max_retries() {
+            assert_eq!(
+                retry(usize::MAX, rate_limited()).await,
+                (Err(rate_limited()), 4, Duration::from_secs(10 + 20 + 40))
+            );
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn other_errors_are_not_retried() {
+            assert_eq!(
+                retry(usize::MAX, NetatmoError::FailedToSendRequest).await,
+                (Err(NetatmoError::FailedToSendRequest), 1, Duration::ZERO)
+            );
+        }
+    }
 }
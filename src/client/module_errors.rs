@@ -0,0 +1,122 @@
+use super::{
+    get_home_status::{HomeStatus, HomeStatusError, HomeStatusErrorCode},
+    ids::ModuleId,
+    module_status::ModuleStatus,
+};
+use crate::errors::{NetatmoError, Result};
+use std::fmt;
+
+impl fmt::Display for HomeStatusErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HomeStatusErrorCode::UnknownError => write!(f, "unknown error"),
+            HomeStatusErrorCode::InternalError => write!(f, "internal error"),
+            HomeStatusErrorCode::ParserError => write!(f, "parser error"),
+            HomeStatusErrorCode::CommandUnknownNodeModuleError => write!(f, "unknown module"),
+            HomeStatusErrorCode::CommandInvalidParams => write!(f, "invalid parameters"),
+            HomeStatusErrorCode::Unreachable => write!(f, "unreachable"),
+            HomeStatusErrorCode::Other(n) => write!(f, "error code {}", n),
+        }
+    }
+}
+
+impl HomeStatusErrorCode {
+    /// Whether asking again later may succeed, e.g. once an unreachable module is back in range.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            HomeStatusErrorCode::InternalError | HomeStatusErrorCode::Unreachable
+        )
+    }
+}
+
+impl From<&HomeStatusError> for NetatmoError {
+    fn from(error: &HomeStatusError) -> Self {
+        NetatmoError::ModuleReportedError {
+            module_id: error.id.clone(),
+            reason: error.code.to_string(),
+        }
+    }
+}
+
+/// An error reported for a module, with the module's status if the home lists it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FailedModule<'a> {
+    pub error: &'a HomeStatusError,
+    pub module: Option<&'a ModuleStatus>,
+}
+
+impl HomeStatus {
+    pub fn errors(&self) -> &[HomeStatusError] {
+        self.body.errors.as_deref().unwrap_or_default()
+    }
+
+    pub fn error_of(&self, module_id: &ModuleId) -> Option<&HomeStatusError> {
+        self.errors().iter().find(|x| &x.id == module_id)
+    }
+
+    pub fn failed_modules(&self) -> Vec<FailedModule<'_>> {
+        let modules = self.body.home.modules.as_deref().unwrap_or_default();
+        self.errors()
+            .iter()
+            .map(|error| FailedModule {
+                error,
+                module: modules.iter().find(|x| x.id() == &error.id),
+            })
+            .collect()
+    }
+
+    /// For strict callers, fails with the first error reported for a module instead of returning a partial status.
+    pub fn into_result(self) -> Result<HomeStatus> {
+        match self.errors().first() {
+            Some(error) => Err(error.into()),
+            None => Ok(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod module_errors {
+        use super::*;
+
+        #[test]
+        fn join_errors_to_modules() {
+            let json = r#"{
+                "status": "ok",
+                "time_server": 1689865621,
+                "body": {
+                  "home": {
+                    "id": "home",
+                    "modules": [
+                      {"id": "12:34:56:00:00:01", "type": "NRV", "firmware_revision": 79},
+                      {"id": "12:34:56:00:00:02", "type": "NRV", "firmware_revision": 79}
+                    ]
+                  },
+                  "errors": [
+                    {"code": 6, "id": "12:34:56:00:00:02"},
+                    {"code": 4, "id": "12:34:56:00:00:09"}
+                  ]
+                }
+              }"#;
+            let home_status: HomeStatus = serde_json::from_str(json).unwrap();
+
+            let failed = home_status.failed_modules();
+            assert_eq!(failed.len(), 2);
+            assert_eq!(failed[0].module.unwrap().id(), "12:34:56:00:00:02");
+            assert!(failed[0].error.code.is_transient());
+            assert_eq!(failed[1].module, None);
+            assert_eq!(home_status.error_of(&ModuleId::new("12:34:56:00:00:01")), None);
+
+            assert_eq!(
+                home_status.into_result(),
+                Err(NetatmoError::ModuleReportedError {
+                    module_id: ModuleId::new("12:34:56:00:00:02"),
+                    reason: "unreachable".to_string(),
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,520 @@
+use super::{
+    get_home_status::{
+        GatewayLinkState, ModuleBatteryState, PairingState, PowerAdapterStatus, SdCardStatus, TagStatus,
+    },
+    ids::ModuleId,
+    module_type::ModuleType,
+    timestamp::{epoch, Epoch},
+};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// The status of a module, with only the fields its kind of module reports.
+///
+/// Deserializes by the `type` field, modules of types without a variant of their own are kept as `Unknown`, as are
+/// modules whose fields don't match their type's variant.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum ModuleStatus {
+    /// NAPlug, OTH
+    Relay(RelayStatus),
+    /// NATherm1, OTM, BNS
+    Thermostat(ThermostatStatus),
+    /// NRV
+    Valve(ValveStatus),
+    /// NACamera, NOC, NDB
+    Camera(CameraStatus),
+    /// NSD, NCO
+    SmokeDetector(SmokeDetectorStatus),
+    /// NACamDoorTag, DTG
+    DoorTag(DoorTagStatus),
+    /// NLG, NMG
+    Gateway(GatewayStatus),
+    /// NLP, NLPM, NLPT, NLPO, NLC, NLM
+    Switch(SwitchStatus),
+    /// NLF, NLFN
+    Dimmer(DimmerStatus),
+    /// NLV, NLLV, NBR, NBO, NBS
+    Shutter(ShutterStatus),
+    /// NLPC, NLE
+    EnergyMeter(EnergyMeterStatus),
+    /// Any other module, or one not matching its type's variant, with the fields most modules report
+    Unknown(UnknownModuleStatus),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct RelayStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub rf_strength: Option<i64>,
+    pub wifi_strength: Option<i64>,
+    pub reachable: Option<bool>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct ThermostatStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub bridge: Option<ModuleId>,
+    pub reachable: Option<bool>,
+    pub rf_strength: Option<i64>,
+    pub battery_level: Option<i64>,
+    pub battery_state: Option<ModuleBatteryState>,
+    pub boiler_status: Option<bool>,
+    pub boiler_valve_comfort_boost: Option<bool>,
+    pub anticipating: Option<bool>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct ValveStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub bridge: Option<ModuleId>,
+    pub reachable: Option<bool>,
+    pub rf_strength: Option<i64>,
+    pub battery_level: Option<i64>,
+    pub battery_state: Option<ModuleBatteryState>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct CameraStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub wifi_strength: Option<i64>,
+    pub vpn_url: Option<String>,
+    pub is_local: Option<bool>,
+    pub sd_status: Option<SdCardStatus>,
+    pub alim_status: Option<PowerAdapterStatus>,
+    pub websocket_connected: Option<bool>,
+    pub max_peers_reached: Option<bool>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SmokeDetectorStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub wifi_strength: Option<i64>,
+    pub wifi_status: Option<i64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub last_seen: Option<Epoch>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub last_wifi_connection: Option<Epoch>,
+    pub status_tampered: Option<bool>,
+    pub test_mode: Option<bool>,
+    pub hush_mode: Option<bool>,
+    pub smoke_detected: Option<bool>,
+    pub detection_chamber_status: Option<String>,
+    pub battery_alarm_state: Option<String>,
+    pub battery_percent: Option<i64>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub last_smoke_detected_start_time: Option<Epoch>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub last_smoke_detected_end_time: Option<Epoch>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct DoorTagStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub bridge: Option<ModuleId>,
+    pub reachable: Option<bool>,
+    pub rf_strength: Option<i64>,
+    pub battery_level: Option<i64>,
+    pub battery_state: Option<ModuleBatteryState>,
+    pub status: Option<TagStatus>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct GatewayStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub hardware_version: Option<i64>,
+    pub wifi_strength: Option<i64>,
+    pub offload: Option<bool>,
+    pub wifi_state: Option<GatewayLinkState>,
+    pub ethernet_state: Option<GatewayLinkState>,
+    pub pairing: Option<PairingState>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SwitchStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub bridge: Option<ModuleId>,
+    pub reachable: Option<bool>,
+    pub rf_strength: Option<i64>,
+    pub on: Option<bool>,
+    /// In W
+    pub power: Option<i64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct DimmerStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub bridge: Option<ModuleId>,
+    pub reachable: Option<bool>,
+    pub rf_strength: Option<i64>,
+    pub on: Option<bool>,
+    /// From 0 to 100
+    pub brightness: Option<i64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct ShutterStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub bridge: Option<ModuleId>,
+    pub reachable: Option<bool>,
+    pub rf_strength: Option<i64>,
+    /// From 0 (closed) to 100 (open)
+    pub current_position: Option<i64>,
+    pub target_position: Option<i64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct EnergyMeterStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub bridge: Option<ModuleId>,
+    pub reachable: Option<bool>,
+    pub rf_strength: Option<i64>,
+    /// In W
+    pub power: Option<i64>,
+    /// In Wh
+    pub sum_energy_elec: Option<i64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct UnknownModuleStatus {
+    pub id: ModuleId,
+    #[serde(rename = "type")]
+    pub type_field: ModuleType,
+    pub firmware_revision: i64,
+    pub bridge: Option<ModuleId>,
+    pub reachable: Option<bool>,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub last_seen: Option<Epoch>,
+    pub rf_strength: Option<i64>,
+    pub wifi_strength: Option<i64>,
+    pub battery_state: Option<ModuleBatteryState>,
+    pub battery_percent: Option<i64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl<'de> Deserialize<'de> for ModuleStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Tagged {
+            #[serde(rename = "type")]
+            type_field: ModuleType,
+        }
+
+        let value = Value::deserialize(deserializer)?;
+        let tagged = Tagged::deserialize(&value).map_err(D::Error::custom)?;
+        let status = match tagged.type_field {
+            ModuleType::NAPlug | ModuleType::OTH => Deserialize::deserialize(&value).map(ModuleStatus::Relay),
+            ModuleType::NATherm1 | ModuleType::OTM | ModuleType::BNS => {
+                Deserialize::deserialize(&value).map(ModuleStatus::Thermostat)
+            }
+            ModuleType::NRV => Deserialize::deserialize(&value).map(ModuleStatus::Valve),
+            ModuleType::NACamera | ModuleType::NOC | ModuleType::NDB => {
+                Deserialize::deserialize(&value).map(ModuleStatus::Camera)
+            }
+            ModuleType::NSD | ModuleType::NCO => Deserialize::deserialize(&value).map(ModuleStatus::SmokeDetector),
+            ModuleType::NACamDoorTag | ModuleType::DTG => Deserialize::deserialize(&value).map(ModuleStatus::DoorTag),
+            ModuleType::NLG | ModuleType::NMG => Deserialize::deserialize(&value).map(ModuleStatus::Gateway),
+            ModuleType::NLP
+            | ModuleType::NLPM
+            | ModuleType::NLPT
+            | ModuleType::NLPO
+            | ModuleType::NLC
+            | ModuleType::NLM => Deserialize::deserialize(&value).map(ModuleStatus::Switch),
+            ModuleType::NLF | ModuleType::NLFN => Deserialize::deserialize(&value).map(ModuleStatus::Dimmer),
+            ref t if t.is_shutter() => Deserialize::deserialize(&value).map(ModuleStatus::Shutter),
+            ModuleType::NLPC | ModuleType::NLE => Deserialize::deserialize(&value).map(ModuleStatus::EnergyMeter),
+            _ => Deserialize::deserialize(&value).map(ModuleStatus::Unknown),
+        };
+
+        status
+            .or_else(|_| serde_json::from_value(value).map(ModuleStatus::Unknown))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl ModuleStatus {
+    pub fn id(&self) -> &ModuleId {
+        match self {
+            ModuleStatus::Relay(x) => &x.id,
+            ModuleStatus::Thermostat(x) => &x.id,
+            ModuleStatus::Valve(x) => &x.id,
+            ModuleStatus::Camera(x) => &x.id,
+            ModuleStatus::SmokeDetector(x) => &x.id,
+            ModuleStatus::DoorTag(x) => &x.id,
+            ModuleStatus::Gateway(x) => &x.id,
+            ModuleStatus::Switch(x) => &x.id,
+            ModuleStatus::Dimmer(x) => &x.id,
+            ModuleStatus::Shutter(x) => &x.id,
+            ModuleStatus::EnergyMeter(x) => &x.id,
+            ModuleStatus::Unknown(x) => &x.id,
+        }
+    }
+
+    pub fn module_type(&self) -> &ModuleType {
+        match self {
+            ModuleStatus::Relay(x) => &x.type_field,
+            ModuleStatus::Thermostat(x) => &x.type_field,
+            ModuleStatus::Valve(x) => &x.type_field,
+            ModuleStatus::Camera(x) => &x.type_field,
+            ModuleStatus::SmokeDetector(x) => &x.type_field,
+            ModuleStatus::DoorTag(x) => &x.type_field,
+            ModuleStatus::Gateway(x) => &x.type_field,
+            ModuleStatus::Switch(x) => &x.type_field,
+            ModuleStatus::Dimmer(x) => &x.type_field,
+            ModuleStatus::Shutter(x) => &x.type_field,
+            ModuleStatus::EnergyMeter(x) => &x.type_field,
+            ModuleStatus::Unknown(x) => &x.type_field,
+        }
+    }
+
+    pub fn firmware_revision(&self) -> i64 {
+        match self {
+            ModuleStatus::Relay(x) => x.firmware_revision,
+            ModuleStatus::Thermostat(x) => x.firmware_revision,
+            ModuleStatus::Valve(x) => x.firmware_revision,
+            ModuleStatus::Camera(x) => x.firmware_revision,
+            ModuleStatus::SmokeDetector(x) => x.firmware_revision,
+            ModuleStatus::DoorTag(x) => x.firmware_revision,
+            ModuleStatus::Gateway(x) => x.firmware_revision,
+            ModuleStatus::Switch(x) => x.firmware_revision,
+            ModuleStatus::Dimmer(x) => x.firmware_revision,
+            ModuleStatus::Shutter(x) => x.firmware_revision,
+            ModuleStatus::EnergyMeter(x) => x.firmware_revision,
+            ModuleStatus::Unknown(x) => x.firmware_revision,
+        }
+    }
+
+    pub fn reachable(&self) -> Option<bool> {
+        match self {
+            ModuleStatus::Relay(x) => x.reachable,
+            ModuleStatus::Thermostat(x) => x.reachable,
+            ModuleStatus::Valve(x) => x.reachable,
+            ModuleStatus::DoorTag(x) => x.reachable,
+            ModuleStatus::Switch(x) => x.reachable,
+            ModuleStatus::Dimmer(x) => x.reachable,
+            ModuleStatus::Shutter(x) => x.reachable,
+            ModuleStatus::EnergyMeter(x) => x.reachable,
+            ModuleStatus::Unknown(x) => x.reachable,
+            ModuleStatus::Camera(_) | ModuleStatus::SmokeDetector(_) | ModuleStatus::Gateway(_) => None,
+        }
+    }
+
+    pub fn last_seen(&self) -> Option<Epoch> {
+        match self {
+            ModuleStatus::SmokeDetector(x) => x.last_seen,
+            ModuleStatus::Unknown(x) => x.last_seen,
+            _ => None,
+        }
+    }
+
+    pub fn rf_strength(&self) -> Option<i64> {
+        match self {
+            ModuleStatus::Relay(x) => x.rf_strength,
+            ModuleStatus::Thermostat(x) => x.rf_strength,
+            ModuleStatus::Valve(x) => x.rf_strength,
+            ModuleStatus::DoorTag(x) => x.rf_strength,
+            ModuleStatus::Switch(x) => x.rf_strength,
+            ModuleStatus::Dimmer(x) => x.rf_strength,
+            ModuleStatus::Shutter(x) => x.rf_strength,
+            ModuleStatus::EnergyMeter(x) => x.rf_strength,
+            ModuleStatus::Unknown(x) => x.rf_strength,
+            ModuleStatus::Camera(_) | ModuleStatus::SmokeDetector(_) | ModuleStatus::Gateway(_) => None,
+        }
+    }
+
+    pub fn wifi_strength(&self) -> Option<i64> {
+        match self {
+            ModuleStatus::Relay(x) => x.wifi_strength,
+            ModuleStatus::Camera(x) => x.wifi_strength,
+            ModuleStatus::SmokeDetector(x) => x.wifi_strength,
+            ModuleStatus::Gateway(x) => x.wifi_strength,
+            ModuleStatus::Unknown(x) => x.wifi_strength,
+            _ => None,
+        }
+    }
+
+    pub fn battery_state(&self) -> Option<&ModuleBatteryState> {
+        match self {
+            ModuleStatus::Thermostat(x) => x.battery_state.as_ref(),
+            ModuleStatus::Valve(x) => x.battery_state.as_ref(),
+            ModuleStatus::DoorTag(x) => x.battery_state.as_ref(),
+            ModuleStatus::Unknown(x) => x.battery_state.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn battery_percent(&self) -> Option<i64> {
+        match self {
+            ModuleStatus::SmokeDetector(x) => x.battery_percent,
+            ModuleStatus::Unknown(x) => x.battery_percent,
+            _ => None,
+        }
+    }
+}
+
+impl DoorTagStatus {
+    /// Whether the door or window the tag is mounted on is open, if the tag reported it.
+    pub fn is_open(&self) -> Option<bool> {
+        match self.status {
+            Some(TagStatus::Open) => Some(true),
+            Some(TagStatus::Closed) => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl GatewayStatus {
+    /// Whether the gateway is connected through wifi or ethernet, if it reported either.
+    pub fn is_gateway_connected(&self) -> Option<bool> {
+        match (&self.wifi_state, &self.ethernet_state) {
+            (None, None) => None,
+            (wifi, ethernet) => Some(
+                [wifi, ethernet]
+                    .iter()
+                    .any(|x| matches!(x, Some(GatewayLinkState::Connected))),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod module_status {
+        use super::*;
+
+        #[test]
+        fn parse_by_type() {
+            let json = r#"[
+                {"id": "12:34:56:00:00:01", "type": "NRV", "firmware_revision": 79, "battery_level": 3000},
+                {"id": "12:34:56:00:00:02", "type": "NLF", "firmware_revision": 60, "on": true, "brightness": 40},
+                {"id": "12:34:56:00:00:03", "type": "NLXYZ", "firmware_revision": 1, "on": false}
+            ]"#;
+
+            let modules: Vec<ModuleStatus> = serde_json::from_str(json).unwrap();
+
+            assert!(matches!(
+                &modules[0],
+                ModuleStatus::Valve(ValveStatus {
+                    battery_level: Some(3000),
+                    ..
+                })
+            ));
+            assert!(matches!(
+                &modules[1],
+                ModuleStatus::Dimmer(DimmerStatus {
+                    brightness: Some(40),
+                    ..
+                })
+            ));
+            assert!(matches!(&modules[2], ModuleStatus::Unknown(x) if x.extra["on"] == false));
+            assert_eq!(modules[2].id(), "12:34:56:00:00:03");
+
+            let json = serde_json::to_value(&modules[1]).unwrap();
+            assert_eq!(json["type"], "NLF");
+            assert_eq!(json["brightness"], 40);
+        }
+
+        #[test]
+        fn malformed_known_type_is_unknown() {
+            let json = r#"[
+                {"id": "12:34:56:00:00:01", "type": "NLF", "firmware_revision": 60, "brightness": "high"},
+                {"id": "12:34:56:00:00:02", "type": "NRV", "firmware_revision": 79, "battery_level": 3000}
+            ]"#;
+
+            let modules: Vec<ModuleStatus> = serde_json::from_str(json).unwrap();
+
+            assert!(matches!(&modules[0], ModuleStatus::Unknown(x) if x.extra["brightness"] == "high"));
+            assert_eq!(modules[0].id(), "12:34:56:00:00:01");
+            assert!(matches!(&modules[1], ModuleStatus::Valve(_)));
+        }
+    }
+}
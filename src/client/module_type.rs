@@ -0,0 +1,329 @@
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// The product a module or device is, as reported in the `type` field of all APIs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum ModuleType {
+    /// Weather station main indoor module
+    NAMain,
+    /// Weather station outdoor module
+    NAModule1,
+    /// Wind gauge
+    NAModule2,
+    /// Rain gauge
+    NAModule3,
+    /// Additional indoor module
+    NAModule4,
+    /// Healthy home coach
+    NHC,
+    /// Thermostat relay
+    NAPlug,
+    /// Thermostat
+    NATherm1,
+    /// Smart radiator valve
+    NRV,
+    /// OpenTherm relay
+    OTH,
+    /// OpenTherm modulating thermostat
+    OTM,
+    /// Smarther thermostat
+    BNS,
+    /// Indoor camera
+    NACamera,
+    /// Outdoor camera
+    NOC,
+    /// Doorbell
+    NDB,
+    /// Smoke detector
+    NSD,
+    /// Indoor siren
+    NIS,
+    /// Carbon monoxide detector
+    NCO,
+    /// Door and window tag of the indoor camera
+    NACamDoorTag,
+    /// Door and window tag
+    DTG,
+    /// Home + Control gateway
+    NLG,
+    /// Smart plug
+    NLP,
+    /// Mobile smart plug
+    NLPM,
+    /// Power switch
+    NLPT,
+    /// Energy meter
+    NLPC,
+    /// Ecometer
+    NLE,
+    /// Roller shutter switch
+    NLV,
+    /// Roller shutter switch with level
+    NLLV,
+    /// Dimmer switch
+    NLF,
+    /// Dimmer switch with neutral
+    NLFN,
+    /// Light micromodule
+    NLM,
+    /// Wireless remote switch
+    NLT,
+    /// Contactor
+    NLPO,
+    /// Cable outlet
+    NLC,
+    /// Muller Intuitiv gateway
+    NMG,
+    /// Muller Intuitiv towel heater
+    NMH,
+    /// Muller Intuitiv radiator
+    NMR,
+    /// Bubendorff roller shutter
+    NBR,
+    /// Bubendorff orientable shutter
+    NBO,
+    /// Bubendorff swinging shutter
+    NBS,
+    Other(String),
+}
+
+impl ModuleType {
+    /// Whether the module heats a room, with its setpoint controlled through the room, e.g. by `set_room_thermpoint`.
+    pub fn is_heating(&self) -> bool {
+        matches!(
+            self,
+            ModuleType::NATherm1
+                | ModuleType::NRV
+                | ModuleType::OTM
+                | ModuleType::BNS
+                | ModuleType::NMH
+                | ModuleType::NMR
+        )
+    }
+
+    /// Whether the module is a shutter, controlled by `set_shutter`.
+    pub fn is_shutter(&self) -> bool {
+        matches!(
+            self,
+            ModuleType::NLV | ModuleType::NLLV | ModuleType::NBR | ModuleType::NBO | ModuleType::NBS
+        )
+    }
+}
+
+/// What a module supports, to pick the controls to show for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum Capability {
+    /// Heats a room whose setpoint can be set, e.g. with `set_room_thermpoint`
+    Setpoint,
+    /// Can be switched on and off, e.g. with `set_plug` or `set_light`
+    OnOff,
+    /// Can be moved to a position, with `set_shutter`
+    Position,
+    /// Can be dimmed, with `set_brightness`
+    Brightness,
+    /// Can be switched between monitoring and not, with `set_camera_monitoring`
+    Monitoring,
+    /// Has a floodlight, set with `set_floodlight`
+    Floodlight,
+    /// Can launch scenarios, with `launch_scenario`
+    Scenario,
+    /// Reports instantaneous power
+    Power,
+    /// Reports consumed energy, also through `get_measure`
+    Energy,
+    /// Reports weather or indoor climate measurements
+    Measurements,
+}
+
+impl ModuleType {
+    pub fn capabilities(&self) -> Vec<Capability> {
+        use Capability::*;
+
+        match self {
+            ModuleType::NAMain
+            | ModuleType::NAModule1
+            | ModuleType::NAModule2
+            | ModuleType::NAModule3
+            | ModuleType::NAModule4
+            | ModuleType::NHC => vec![Measurements],
+            ModuleType::NATherm1 | ModuleType::NRV | ModuleType::OTM | ModuleType::BNS => vec![Setpoint],
+            ModuleType::NMH | ModuleType::NMR => vec![Setpoint, Power],
+            ModuleType::NACamera => vec![Monitoring],
+            ModuleType::NOC => vec![Monitoring, Floodlight],
+            ModuleType::NLG => vec![Scenario],
+            ModuleType::NLP | ModuleType::NLPM | ModuleType::NLPT => vec![OnOff, Power, Energy],
+            ModuleType::NLPC | ModuleType::NLE => vec![Power, Energy],
+            ModuleType::NLV | ModuleType::NLLV | ModuleType::NBR | ModuleType::NBO | ModuleType::NBS => vec![Position],
+            ModuleType::NLF | ModuleType::NLFN => vec![OnOff, Brightness, Power],
+            ModuleType::NLM | ModuleType::NLPO | ModuleType::NLC => vec![OnOff],
+            _ => vec![],
+        }
+    }
+
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.capabilities().contains(&capability)
+    }
+}
+
+impl Default for ModuleType {
+    fn default() -> Self {
+        ModuleType::Other(String::new())
+    }
+}
+
+impl fmt::Display for ModuleType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            ModuleType::NAMain => "NAMain",
+            ModuleType::NAModule1 => "NAModule1",
+            ModuleType::NAModule2 => "NAModule2",
+            ModuleType::NAModule3 => "NAModule3",
+            ModuleType::NAModule4 => "NAModule4",
+            ModuleType::NHC => "NHC",
+            ModuleType::NAPlug => "NAPlug",
+            ModuleType::NATherm1 => "NATherm1",
+            ModuleType::NRV => "NRV",
+            ModuleType::OTH => "OTH",
+            ModuleType::OTM => "OTM",
+            ModuleType::BNS => "BNS",
+            ModuleType::NACamera => "NACamera",
+            ModuleType::NOC => "NOC",
+            ModuleType::NDB => "NDB",
+            ModuleType::NSD => "NSD",
+            ModuleType::NIS => "NIS",
+            ModuleType::NCO => "NCO",
+            ModuleType::NACamDoorTag => "NACamDoorTag",
+            ModuleType::DTG => "DTG",
+            ModuleType::NLG => "NLG",
+            ModuleType::NLP => "NLP",
+            ModuleType::NLPM => "NLPM",
+            ModuleType::NLPT => "NLPT",
+            ModuleType::NLPC => "NLPC",
+            ModuleType::NLE => "NLE",
+            ModuleType::NLV => "NLV",
+            ModuleType::NLLV => "NLLV",
+            ModuleType::NLF => "NLF",
+            ModuleType::NLFN => "NLFN",
+            ModuleType::NLM => "NLM",
+            ModuleType::NLT => "NLT",
+            ModuleType::NLPO => "NLPO",
+            ModuleType::NLC => "NLC",
+            ModuleType::NMG => "NMG",
+            ModuleType::NMH => "NMH",
+            ModuleType::NMR => "NMR",
+            ModuleType::NBR => "NBR",
+            ModuleType::NBO => "NBO",
+            ModuleType::NBS => "NBS",
+            ModuleType::Other(s) => s,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl From<&str> for ModuleType {
+    fn from(s: &str) -> Self {
+        match s {
+            "NAMain" => ModuleType::NAMain,
+            "NAModule1" => ModuleType::NAModule1,
+            "NAModule2" => ModuleType::NAModule2,
+            "NAModule3" => ModuleType::NAModule3,
+            "NAModule4" => ModuleType::NAModule4,
+            "NHC" => ModuleType::NHC,
+            "NAPlug" => ModuleType::NAPlug,
+            "NATherm1" => ModuleType::NATherm1,
+            "NRV" => ModuleType::NRV,
+            "OTH" => ModuleType::OTH,
+            "OTM" => ModuleType::OTM,
+            "BNS" => ModuleType::BNS,
+            "NACamera" => ModuleType::NACamera,
+            "NOC" => ModuleType::NOC,
+            "NDB" => ModuleType::NDB,
+            "NSD" => ModuleType::NSD,
+            "NIS" => ModuleType::NIS,
+            "NCO" => ModuleType::NCO,
+            "NACamDoorTag" => ModuleType::NACamDoorTag,
+            "DTG" => ModuleType::DTG,
+            "NLG" => ModuleType::NLG,
+            "NLP" => ModuleType::NLP,
+            "NLPM" => ModuleType::NLPM,
+            "NLPT" => ModuleType::NLPT,
+            "NLPC" => ModuleType::NLPC,
+            "NLE" => ModuleType::NLE,
+            "NLV" => ModuleType::NLV,
+            "NLLV" => ModuleType::NLLV,
+            "NLF" => ModuleType::NLF,
+            "NLFN" => ModuleType::NLFN,
+            "NLM" => ModuleType::NLM,
+            "NLT" => ModuleType::NLT,
+            "NLPO" => ModuleType::NLPO,
+            "NLC" => ModuleType::NLC,
+            "NMG" => ModuleType::NMG,
+            "NMH" => ModuleType::NMH,
+            "NMR" => ModuleType::NMR,
+            "NBR" => ModuleType::NBR,
+            "NBO" => ModuleType::NBO,
+            "NBS" => ModuleType::NBS,
+            _ => ModuleType::Other(s.to_string()),
+        }
+    }
+}
+
+impl From<String> for ModuleType {
+    fn from(s: String) -> Self {
+        ModuleType::from(s.as_str())
+    }
+}
+
+impl From<ModuleType> for String {
+    fn from(t: ModuleType) -> Self {
+        t.to_string()
+    }
+}
+
+impl FromStr for ModuleType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(ModuleType::from(s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::assert_round_trip;
+
+    mod module_type {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let types: Vec<ModuleType> = serde_json::from_str(r#"["NAMain", "NLP", "NXYZ"]"#).unwrap();
+
+            assert_eq!(
+                types,
+                vec![
+                    ModuleType::NAMain,
+                    ModuleType::NLP,
+                    ModuleType::Other("NXYZ".to_string())
+                ]
+            );
+            assert_eq!(serde_json::to_string(&types).unwrap(), r#"["NAMain","NLP","NXYZ"]"#);
+            for t in types {
+                assert_round_trip!(t);
+            }
+        }
+
+        #[test]
+        fn capabilities() {
+            assert!(ModuleType::NLFN.supports(Capability::Brightness));
+            assert!(ModuleType::NBR.supports(Capability::Position));
+            assert!(ModuleType::Other("NXYZ".to_string()).capabilities().is_empty());
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use super::{
+    get_home_status::{get_home_status, GetHomeStatusParameters, PersonStatus},
+    get_homes_data::{get_homes_data, GetHomesDataParameters, Person},
+    ids::HomeId,
+};
+use crate::{client::NetatmoClient, errors::Result};
+
+/// Returns the persons currently at home, i.e. those a camera saw arriving and who aren't `out_of_sight`.
+pub fn persons_at_home(persons: &[Person], statuses: &[PersonStatus]) -> Vec<Person> {
+    persons
+        .iter()
+        .filter(|person| {
+            statuses
+                .iter()
+                .any(|status| status.id == person.id && status.out_of_sight == Some(false))
+        })
+        .cloned()
+        .collect()
+}
+
+pub async fn get_persons_at_home(client: &NetatmoClient, home_id: &HomeId) -> Result<Vec<Person>> {
+    let homes_data = get_homes_data(client, &GetHomesDataParameters::new().home_id(home_id)).await?;
+    let home_status = get_home_status(client, &GetHomeStatusParameters::new().home_id(home_id)).await?;
+
+    let persons = homes_data
+        .body
+        .homes
+        .unwrap_or_default()
+        .into_iter()
+        .find(|x| &x.id == home_id)
+        .and_then(|x| x.persons)
+        .unwrap_or_default();
+    let statuses = home_status.body.home.persons.unwrap_or_default();
+
+    Ok(persons_at_home(&persons, &statuses))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{
+        ids::PersonId,
+        test_util::{at, TIME},
+    };
+
+    mod persons_at_home {
+        use super::*;
+
+        #[test]
+        fn only_persons_in_sight() {
+            let persons = vec![
+                Person {
+                    id: PersonId::new("1"),
+                    pseudo: Some("John".to_string()),
+                    face_url: None,
+                },
+                Person {
+                    id: PersonId::new("2"),
+                    pseudo: Some("Jane".to_string()),
+                    face_url: None,
+                },
+                Person {
+                    id: PersonId::new("3"),
+                    pseudo: None,
+                    face_url: None,
+                },
+            ];
+            let statuses = vec![
+                PersonStatus {
+                    id: PersonId::new("1"),
+                    last_seen: Some(at(TIME)),
+                    out_of_sight: Some(false),
+                },
+                PersonStatus {
+                    id: PersonId::new("2"),
+                    last_seen: Some(at(TIME)),
+                    out_of_sight: Some(true),
+                },
+            ];
+
+            let at_home = persons_at_home(&persons, &statuses);
+
+            assert_eq!(at_home, vec![persons[0].clone()]);
+        }
+    }
+}
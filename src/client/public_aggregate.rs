@@ -0,0 +1,158 @@
+use super::get_public_data::{PublicData, PublicStation};
+use serde::{Deserialize, Serialize};
+
+// Values further than this many scaled median absolute deviations from the median are rejected as outliers
+const OUTLIER_THRESHOLD: f64 = 3.0;
+// Scales the median absolute deviation to estimate the standard deviation of normally distributed values
+const MAD_SCALE: f64 = 1.4826;
+
+/// Aggregates over the public stations of a region, after rejecting outliers such as sensors placed in the sun.
+///
+/// Rain is summed over all stations without rejecting outliers, as it is usually local to a few of them.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct RegionalAggregate {
+    pub station_count: usize,
+    pub median_temperature: Option<f64>,
+    pub median_humidity: Option<f64>,
+    pub median_pressure: Option<f64>,
+    pub total_rain_60min: f64,
+    pub total_rain_24h: f64,
+}
+
+impl PublicStation {
+    /// The most recent value of a measurement type such as `temperature`, `humidity` or `pressure`.
+    pub fn latest(&self, measurement_type: &str) -> Option<f64> {
+        self.measures.values().find_map(|measure| {
+            let index = measure.types.as_ref()?.iter().position(|x| x == measurement_type)?;
+            let (_, values) = measure
+                .res
+                .as_ref()?
+                .iter()
+                .max_by_key(|(timestamp, _)| timestamp.parse::<i64>().unwrap_or_default())?;
+            values.get(index).copied().flatten()
+        })
+    }
+
+    pub fn rain_60min(&self) -> Option<f64> {
+        self.measures.values().find_map(|x| x.rain_60min)
+    }
+
+    pub fn rain_24h(&self) -> Option<f64> {
+        self.measures.values().find_map(|x| x.rain_24h)
+    }
+}
+
+impl PublicData {
+    pub fn aggregate(&self) -> RegionalAggregate {
+        let values =
+            |f: &dyn Fn(&PublicStation) -> Option<f64>| reject_outliers(self.body.iter().filter_map(f).collect());
+        let total = |f: &dyn Fn(&PublicStation) -> Option<f64>| self.body.iter().filter_map(f).sum();
+
+        RegionalAggregate {
+            station_count: self.body.len(),
+            median_temperature: median(&values(&|x| x.latest("temperature"))),
+            median_humidity: median(&values(&|x| x.latest("humidity"))),
+            median_pressure: median(&values(&|x| x.latest("pressure"))),
+            total_rain_60min: total(&|x| x.rain_60min()),
+            total_rain_24h: total(&|x| x.rain_24h()),
+        }
+    }
+}
+
+fn median(values: &[f64]) -> Option<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        x if x % 2 == 0 => Some((sorted[mid - 1] + sorted[mid]) / 2.0),
+        _ => Some(sorted[mid]),
+    }
+}
+
+fn reject_outliers(values: Vec<f64>) -> Vec<f64> {
+    let Some(center) = median(&values) else {
+        return values;
+    };
+    let deviations: Vec<f64> = values.iter().map(|x| (x - center).abs()).collect();
+    let mad = median(&deviations).unwrap_or_default() * MAD_SCALE;
+    // At least half the values equal the median, which leaves no spread to judge the others by
+    if mad == 0.0 {
+        return values;
+    }
+
+    values
+        .into_iter()
+        .filter(|x| (x - center).abs() <= OUTLIER_THRESHOLD * mad)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::get_public_data::PublicMeasure;
+    use crate::client::{geo::Coordinates, test_util::public_data};
+    use std::collections::HashMap;
+
+    mod aggregate {
+        use super::*;
+
+        fn station(temperature: f64, rain_24h: f64) -> PublicStation {
+            let mut measures = HashMap::new();
+            measures.insert(
+                "02:00:00:00:00:01".to_string(),
+                PublicMeasure {
+                    res: Some(HashMap::from([("1556451224".to_string(), vec![Some(temperature)])])),
+                    types: Some(vec!["temperature".to_string()]),
+                    ..PublicMeasure::default()
+                },
+            );
+            measures.insert(
+                "05:00:00:00:00:01".to_string(),
+                PublicMeasure {
+                    rain_24h: Some(rain_24h),
+                    ..PublicMeasure::default()
+                },
+            );
+
+            PublicStation {
+                measures,
+                ..public_data::station("70:ee:50:00:00:01", Coordinates::new(48.85, 2.35))
+            }
+        }
+
+        #[test]
+        fn rejects_outliers() {
+            let data = public_data::data(vec![
+                station(14.0, 3.0),
+                station(14.5, 3.2),
+                station(15.0, 2.8),
+                station(38.0, 3.1),
+            ]);
+
+            let aggregate = data.aggregate();
+
+            assert_eq!(aggregate.station_count, 4);
+            assert_eq!(aggregate.median_temperature, Some(14.5));
+            assert!((aggregate.total_rain_24h - 12.1).abs() < 1e-9);
+        }
+
+        #[test]
+        fn keeps_local_rain() {
+            let data = public_data::data(vec![
+                station(14.0, 0.0),
+                station(14.0, 0.0),
+                station(14.0, 0.0),
+                station(14.5, 4.2),
+            ]);
+
+            let aggregate = data.aggregate();
+
+            assert_eq!(aggregate.median_temperature, Some(14.0));
+            assert!((aggregate.total_rain_24h - 4.2).abs() < 1e-9);
+        }
+    }
+}
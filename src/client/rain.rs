@@ -0,0 +1,104 @@
+use super::{get_measure::Type, time_series::TimeSeries};
+use serde::{Deserialize, Serialize};
+
+const HOUR: i64 = 60 * 60;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+
+/// Rain fallen since the start of the last hour, local day and local week (starting on Monday), in mm.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct RainAccumulation {
+    pub last_hour: f64,
+    pub today: f64,
+    pub this_week: f64,
+}
+
+impl TimeSeries {
+    /// The rain fallen in the interval starting at each timestamp.
+    ///
+    /// Both `sum_rain` and `Rain` (at scale `max`) are totals of their interval, not running totals like the dashboard's
+    /// `sum_rain_24`, so accumulating means summing intervals. `sum_rain` is preferred if both were requested.
+    pub fn rain_intervals(&self) -> Vec<(i64, f64)> {
+        if self.types().contains(&Type::SumRain) {
+            self.get(&Type::SumRain)
+        } else {
+            self.get(&Type::Rain)
+        }
+    }
+
+    /// The rain fallen in the intervals starting within `start..end`.
+    pub fn accumulated_rain(&self, start: i64, end: i64) -> f64 {
+        self.rain_intervals()
+            .into_iter()
+            .filter(|(t, _)| (start..end).contains(t))
+            .map(|(_, v)| v)
+            .sum()
+    }
+
+    /// For each timestamp, the rain fallen within the `window` seconds up to and including its interval.
+    pub fn rolling_rain(&self, window: i64) -> Vec<(i64, f64)> {
+        let intervals = self.rain_intervals();
+        intervals
+            .iter()
+            .map(|(t, _)| {
+                let sum = intervals
+                    .iter()
+                    .filter(|(x, _)| *x <= *t && *x + window > *t)
+                    .map(|(_, v)| v)
+                    .sum();
+                (*t, sum)
+            })
+            .collect()
+    }
+
+    /// Accumulates rain up to `now`, aligning days and weeks to the local time at `utc_offset` seconds from UTC.
+    pub fn rain_accumulation(&self, now: i64, utc_offset: i64) -> RainAccumulation {
+        let local = now + utc_offset;
+        let start_of_day = local - local.rem_euclid(DAY) - utc_offset;
+        // 1970-01-01 was a Thursday, shift by three days to start weeks on Monday
+        let start_of_week = local - (local + 3 * DAY).rem_euclid(WEEK) - utc_offset;
+        let end = now + 1;
+
+        RainAccumulation {
+            last_hour: self.accumulated_rain(now - HOUR, end),
+            today: self.accumulated_rain(start_of_day, end),
+            this_week: self.accumulated_rain(start_of_week, end),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod rain_accumulation {
+        use super::*;
+
+        #[test]
+        fn calendar_aligned() {
+            // Wednesday 2023-07-19 23:30 UTC, which is already Thursday 01:30 at UTC+2
+            let now = 1689809400;
+            let series = TimeSeries::new(
+                &[Type::SumRain],
+                vec![
+                    (now - 3 * DAY, vec![Some(4.0)]),
+                    (now - 3 * HOUR, vec![Some(2.0)]),
+                    (now - 1800, vec![Some(0.5)]),
+                ],
+            );
+
+            let accumulation = series.rain_accumulation(now, 2 * HOUR);
+
+            assert_eq!(
+                accumulation,
+                RainAccumulation {
+                    last_hour: 0.5,
+                    today: 0.5,
+                    this_week: 6.5,
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use super::{get_homecoachs_data::HomeCoach, get_station_data, module_status::ModuleStatus, timestamp::Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// Whether a module can currently be reached, from its `reachable` flag and the time it was last heard from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Reachability {
+    Reachable,
+    Unreachable,
+    /// Not heard from for longer than the staleness threshold, last at `since`
+    Stale {
+        since: i64,
+    },
+    /// Neither the flag nor the time of the last contact is reported
+    Unknown,
+}
+
+impl Reachability {
+    /// Assesses a module at `now`; it is stale once `stale_after` seconds passed since `last_seen`, even if the API
+    /// still claims it is reachable, and reachable without a flag if it was seen recently.
+    pub fn assess(reachable: Option<bool>, last_seen: Option<i64>, now: i64, stale_after: i64) -> Self {
+        match (reachable, last_seen) {
+            (Some(false), _) => Reachability::Unreachable,
+            (_, Some(since)) if now - since > stale_after => Reachability::Stale { since },
+            (Some(true), _) | (None, Some(_)) => Reachability::Reachable,
+            (None, None) => Reachability::Unknown,
+        }
+    }
+
+    pub fn is_reachable(&self) -> bool {
+        *self == Reachability::Reachable
+    }
+}
+
+impl ModuleStatus {
+    /// Cameras and gateways report neither, so their reachability is always unknown.
+    pub fn reachability(&self, now: i64, stale_after: i64) -> Reachability {
+        Reachability::assess(
+            self.reachable(),
+            self.last_seen().map(|x| x.epoch_seconds()),
+            now,
+            stale_after,
+        )
+    }
+}
+
+impl get_station_data::Device {
+    pub fn reachability(&self, now: i64, stale_after: i64) -> Reachability {
+        Reachability::assess(
+            Some(self.reachable),
+            Some(self.last_status_store.epoch_seconds()),
+            now,
+            stale_after,
+        )
+    }
+}
+
+impl get_station_data::Module {
+    pub fn reachability(&self, now: i64, stale_after: i64) -> Reachability {
+        let last_seen = self.last_seen.max(self.last_message);
+        Reachability::assess(Some(self.reachable), Some(last_seen.epoch_seconds()), now, stale_after)
+    }
+}
+
+impl HomeCoach {
+    pub fn reachability(&self, now: i64, stale_after: i64) -> Reachability {
+        Reachability::assess(
+            Some(self.reachable),
+            Some(self.last_status_store.epoch_seconds()),
+            now,
+            stale_after,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod reachability {
+        use super::*;
+
+        #[test]
+        fn last_seen_fallback() {
+            let now = 1689865621;
+            let module = |json: &str| serde_json::from_str::<ModuleStatus>(json).unwrap();
+
+            let reachable = module(r#"{"id": "1", "type": "NRV", "firmware_revision": 1, "reachable": true}"#);
+            let unreachable = module(r#"{"id": "2", "type": "NRV", "firmware_revision": 1, "reachable": false}"#);
+            let recent = module(r#"{"id": "3", "type": "NSD", "firmware_revision": 1, "last_seen": 1689865000}"#);
+            let stale = module(
+                r#"{"id": "4", "type": "NSD", "firmware_revision": 1, "reachable": true, "last_seen": 1689800000}"#,
+            );
+            let unknown = module(r#"{"id": "5", "type": "NACamera", "firmware_revision": 1}"#);
+
+            assert_eq!(reachable.reachability(now, 3600), Reachability::Reachable);
+            assert_eq!(unreachable.reachability(now, 3600), Reachability::Unreachable);
+            assert_eq!(recent.reachability(now, 3600), Reachability::Reachable);
+            assert_eq!(stale.reachability(now, 3600), Reachability::Stale { since: 1689800000 });
+            assert_eq!(stale.reachability(now, 86400), Reachability::Reachable);
+            assert_eq!(unknown.reachability(now, 3600), Reachability::Unknown);
+        }
+    }
+}
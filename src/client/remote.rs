@@ -0,0 +1,36 @@
+use super::get_events::{Event, SecurityEventType};
+
+impl Event {
+    /// The `sub_type` of a press on a wireless remote switch (NLT), which tells its buttons and short and long presses
+    /// apart. Netatmo doesn't document the values, so it is kept as reported.
+    pub fn button_press(&self) -> Option<i64> {
+        match self.type_field {
+            SecurityEventType::ButtonPress => self.sub_type,
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod button_press {
+        use super::*;
+
+        #[test]
+        fn parse_sub_type() {
+            let json = r#"{
+                "id": "...",
+                "type": "button_press",
+                "time": 1689865621,
+                "module_id": "00:04:74:00:00:01",
+                "sub_type": 3
+              }"#;
+
+            let event: Event = serde_json::from_str(json).unwrap();
+
+            assert_eq!(event.button_press(), Some(3));
+        }
+    }
+}
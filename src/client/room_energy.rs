@@ -0,0 +1,162 @@
+use super::{
+    get_homes_data::{Home, Module},
+    get_measure::{get_measure_range, GetMeasureParameters, Scale, Type},
+    ids::RoomId,
+    mac_address::MacAddress,
+    module_type::Capability,
+    time_series::TimeSeries,
+};
+use crate::{client::NetatmoClient, errors::Result};
+use std::collections::HashMap;
+
+impl Home {
+    /// The modules metering energy, grouped by the id of the room they are in.
+    pub fn energy_modules_by_room(&self) -> HashMap<&RoomId, Vec<&Module>> {
+        let mut rooms: HashMap<&RoomId, Vec<&Module>> = HashMap::new();
+        for module in self.modules.iter().flatten() {
+            if let (Some(room_id), true) = (&module.room_id, module.type_field.supports(Capability::Energy)) {
+                rooms.entry(room_id).or_default().push(module);
+            }
+        }
+
+        rooms
+    }
+}
+
+/// Sums the energy consumed between `start` and `end` by the plugs and meters of each room of a home, in kWh.
+///
+/// The modules are measured one after the other, and the whole call fails as soon as measuring one of them fails, since
+/// leaving a module out would understate its room's consumption.
+pub async fn room_energy(client: &NetatmoClient, home: &Home, start: i64, end: i64) -> Result<HashMap<RoomId, f64>> {
+    let mut measured = vec![];
+    for (room_id, parameters) in energy_measures(home)? {
+        let series = get_measure_range(client, &parameters, start, end).await?;
+        measured.push((room_id, series));
+    }
+
+    Ok(sum_by_room(measured))
+}
+
+/// The parameters to measure the energy of each module metering it, with the id of the room it is in.
+fn energy_measures(home: &Home) -> Result<Vec<(&RoomId, GetMeasureParameters)>> {
+    let mut measures = vec![];
+    for (room_id, modules) in home.energy_modules_by_room() {
+        for module in modules {
+            let module_id = MacAddress::try_from(&module.id)?;
+            let device_id = match &module.bridge {
+                Some(bridge) => MacAddress::try_from(bridge)?,
+                None => module_id.clone(),
+            };
+            let parameters =
+                GetMeasureParameters::with_module_id(&device_id, &module_id, Scale::Hour1, &[Type::SumEnergyElec]);
+            measures.push((room_id, parameters));
+        }
+    }
+
+    Ok(measures)
+}
+
+/// Sums the energy of the series measured for each room, in kWh.
+fn sum_by_room<'a>(measured: impl IntoIterator<Item = (&'a RoomId, TimeSeries)>) -> HashMap<RoomId, f64> {
+    let mut consumption: HashMap<RoomId, f64> = HashMap::new();
+    for (room_id, series) in measured {
+        let wh = series.energy().iter().map(|(_, v)| v).sum::<f64>();
+        *consumption.entry(room_id.clone()).or_default() += wh / 1000.0;
+    }
+
+    consumption
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{get_homes_data::Home, ids::ModuleId, module_type::ModuleType, test_util::homes_data};
+
+    mod room_energy {
+        use super::*;
+
+        fn module(id: &str, type_field: ModuleType, room_id: Option<&str>, bridge: Option<&str>) -> Module {
+            Module {
+                room_id: room_id.map(RoomId::new),
+                bridge: bridge.map(ModuleId::new),
+                ..homes_data::module(id, type_field, id)
+            }
+        }
+
+        #[test]
+        fn measures_energy_modules() {
+            let home = Home {
+                modules: Some(vec![
+                    module("70:ee:50:00:00:10", ModuleType::NLG, None, None),
+                    module(
+                        "12:34:56:00:00:01",
+                        ModuleType::NLP,
+                        Some("1"),
+                        Some("70:ee:50:00:00:10"),
+                    ),
+                    module("12:34:56:00:00:02", ModuleType::NLPC, Some("1"), None),
+                    module(
+                        "12:34:56:00:00:03",
+                        ModuleType::NLP,
+                        Some("2"),
+                        Some("70:ee:50:00:00:10"),
+                    ),
+                    module("12:34:56:00:00:04", ModuleType::NLP, None, Some("70:ee:50:00:00:10")),
+                    module(
+                        "12:34:56:00:00:05",
+                        ModuleType::NLV,
+                        Some("1"),
+                        Some("70:ee:50:00:00:10"),
+                    ),
+                ]),
+                ..homes_data::home("home", "Home", "Europe/Paris")
+            };
+            let parameters = |device_id: &str, module_id: &str| {
+                GetMeasureParameters::with_module_id(
+                    &device_id.parse().unwrap(),
+                    &module_id.parse().unwrap(),
+                    Scale::Hour1,
+                    &[Type::SumEnergyElec],
+                )
+            };
+
+            let mut measures = energy_measures(&home).unwrap();
+            measures.sort_by(|a, b| a.1.module_id().cmp(b.1.module_id()));
+
+            let (first, second) = (RoomId::new("1"), RoomId::new("2"));
+            assert_eq!(
+                measures,
+                [
+                    (&first, parameters("70:ee:50:00:00:10", "12:34:56:00:00:01")),
+                    (&first, parameters("12:34:56:00:00:02", "12:34:56:00:00:02")),
+                    (&second, parameters("70:ee:50:00:00:10", "12:34:56:00:00:03")),
+                ]
+            );
+        }
+
+        #[test]
+        fn sums_per_room() {
+            let series = |values: &[f64]| {
+                TimeSeries::new(
+                    &[Type::SumEnergyElec],
+                    values
+                        .iter()
+                        .enumerate()
+                        .map(|(i, x)| (i as i64 * 3600, vec![Some(*x)]))
+                        .collect::<Vec<_>>(),
+                )
+            };
+
+            let (first, second) = (RoomId::new("1"), RoomId::new("2"));
+            let consumption = sum_by_room([
+                (&first, series(&[1000.0, 500.0])),
+                (&second, series(&[])),
+                (&first, series(&[250.0])),
+            ]);
+
+            assert_eq!(consumption.len(), 2);
+            assert_eq!(consumption["1"], 1.75);
+            assert_eq!(consumption["2"], 0.0);
+        }
+    }
+}
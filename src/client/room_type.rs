@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// The category a room was given in the app, as reported in the `type` field of a home's rooms.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum RoomType {
+    Bathroom,
+    Bedroom,
+    Corridor,
+    Custom,
+    DiningRoom,
+    ElectricalCabinet,
+    Garage,
+    HomeOffice,
+    Kitchen,
+    LivingRoom,
+    Lobby,
+    Outdoor,
+    Toilets,
+    Other(String),
+}
+
+impl Default for RoomType {
+    fn default() -> Self {
+        RoomType::Other(String::new())
+    }
+}
+
+impl fmt::Display for RoomType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            RoomType::Bathroom => "bathroom",
+            RoomType::Bedroom => "bedroom",
+            RoomType::Corridor => "corridor",
+            RoomType::Custom => "custom",
+            RoomType::DiningRoom => "dining_room",
+            RoomType::ElectricalCabinet => "electrical_cabinet",
+            RoomType::Garage => "garage",
+            RoomType::HomeOffice => "home_office",
+            RoomType::Kitchen => "kitchen",
+            RoomType::LivingRoom => "livingroom",
+            RoomType::Lobby => "lobby",
+            RoomType::Outdoor => "outdoor",
+            RoomType::Toilets => "toilets",
+            RoomType::Other(s) => s,
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl From<String> for RoomType {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "bathroom" => RoomType::Bathroom,
+            "bedroom" => RoomType::Bedroom,
+            "corridor" => RoomType::Corridor,
+            "custom" => RoomType::Custom,
+            "dining_room" => RoomType::DiningRoom,
+            "electrical_cabinet" => RoomType::ElectricalCabinet,
+            "garage" => RoomType::Garage,
+            "home_office" => RoomType::HomeOffice,
+            "kitchen" => RoomType::Kitchen,
+            "livingroom" => RoomType::LivingRoom,
+            "lobby" => RoomType::Lobby,
+            "outdoor" => RoomType::Outdoor,
+            "toilets" => RoomType::Toilets,
+            _ => RoomType::Other(s),
+        }
+    }
+}
+
+impl From<RoomType> for String {
+    fn from(t: RoomType) -> Self {
+        t.to_string()
+    }
+}
+
+impl FromStr for RoomType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(RoomType::from(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::assert_round_trip;
+
+    mod room_type {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let types: Vec<RoomType> = serde_json::from_str(r#"["livingroom", "dining_room", "attic"]"#).unwrap();
+
+            assert_eq!(
+                types,
+                vec![
+                    RoomType::LivingRoom,
+                    RoomType::DiningRoom,
+                    RoomType::Other("attic".to_string())
+                ]
+            );
+            assert_eq!(
+                serde_json::to_string(&types).unwrap(),
+                r#"["livingroom","dining_room","attic"]"#
+            );
+            for t in types {
+                assert_round_trip!(t);
+            }
+        }
+    }
+}
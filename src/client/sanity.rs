@@ -0,0 +1,211 @@
+use super::{
+    get_home_status::Room, get_measure::Type, get_station_data::DashboardData, time_series::TimeSeries,
+    timestamp::Timestamp,
+};
+use serde::{Deserialize, Serialize};
+
+/// Plausible ranges of values per measurement type, outside of which a reading is treated as bogus, e.g. the -3276.8°C
+/// sensors occasionally report. Types without bounds only reject NaN and infinite values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SanityBounds {
+    bounds: Vec<(Type, f64, f64)>,
+}
+
+impl Default for SanityBounds {
+    /// Bounds wide enough for any station's surroundings, in the units the APIs report values in.
+    fn default() -> Self {
+        SanityBounds::empty()
+            .bound(&[Type::Temperature, Type::MinTemp, Type::MaxTemp], -90.0, 70.0)
+            .bound(&[Type::Humidity, Type::MinHum, Type::MaxHum], 0.0, 100.0)
+            .bound(&[Type::CO2, Type::MinCO2, Type::MaxCO2], 0.0, 10000.0)
+            .bound(&[Type::Pressure, Type::MinPressure, Type::MaxPressure], 500.0, 1100.0)
+            .bound(&[Type::Noise, Type::MinNoise, Type::MaxNoise], 0.0, 140.0)
+            .bound(&[Type::Rain, Type::SumRain], 0.0, 1000.0)
+            .bound(&[Type::WindStrength, Type::GustStrength], 0.0, 400.0)
+            .bound(&[Type::WindAngle, Type::GustAngle], 0.0, 360.0)
+    }
+}
+
+impl SanityBounds {
+    /// Bounds that only reject NaN and infinite values.
+    pub fn empty() -> Self {
+        SanityBounds { bounds: vec![] }
+    }
+
+    /// Sets the inclusive range of plausible values of the types, replacing their previous bounds.
+    pub fn bound(mut self, types: &[Type], min: f64, max: f64) -> Self {
+        self.bounds.retain(|(t, _, _)| !types.contains(t));
+        self.bounds.extend(types.iter().map(|t| (t.clone(), min, max)));
+        self
+    }
+
+    pub fn get(&self, measurement_type: &Type) -> Option<(f64, f64)> {
+        self.bounds
+            .iter()
+            .find(|(t, _, _)| t == measurement_type)
+            .map(|(_, min, max)| (*min, *max))
+    }
+
+    pub fn is_plausible(&self, measurement_type: &Type, value: f64) -> bool {
+        value.is_finite()
+            && self
+                .get(measurement_type)
+                .is_none_or(|(min, max)| (min..=max).contains(&value))
+    }
+
+    fn check(&self, measurement_type: &Type, value: Option<f64>) -> Option<f64> {
+        value.filter(|x| self.is_plausible(measurement_type, *x))
+    }
+
+    fn check_integer(&self, measurement_type: &Type, value: Option<u64>) -> Option<u64> {
+        value.filter(|x| self.is_plausible(measurement_type, *x as f64))
+    }
+}
+
+/// A value outside of the bounds of its type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Outlier {
+    pub timestamp: Option<i64>,
+    pub measurement_type: Type,
+    pub value: f64,
+}
+
+impl TimeSeries {
+    pub fn outliers(&self, bounds: &SanityBounds) -> Vec<Outlier> {
+        self.points()
+            .flat_map(|point| {
+                point.values().filter_map(move |(t, v)| match v {
+                    Some(value) if !bounds.is_plausible(t, value) => Some(Outlier {
+                        timestamp: Some(point.timestamp),
+                        measurement_type: t.clone(),
+                        value,
+                    }),
+                    _ => None,
+                })
+            })
+            .collect()
+    }
+
+    /// Removes the values outside of `bounds`, keeping their points.
+    pub fn sanitized(&self, bounds: &SanityBounds) -> TimeSeries {
+        let values = self.points().map(|point| {
+            let values = point.values().map(|(t, v)| bounds.check(t, v)).collect();
+            (point.timestamp, values)
+        });
+        TimeSeries::new(self.types(), values)
+    }
+}
+
+impl DashboardData {
+    fn readings(&self) -> Vec<(Type, Option<f64>)> {
+        vec![
+            (Type::Temperature, self.temperature),
+            (Type::MinTemp, self.min_temp),
+            (Type::MaxTemp, self.max_temp),
+            (Type::Humidity, self.humidity.map(|x| x as f64)),
+            (Type::CO2, self.co2.map(|x| x as f64)),
+            (Type::Noise, self.noise.map(|x| x as f64)),
+            (Type::Pressure, self.pressure),
+            (Type::Pressure, self.absolute_pressure),
+            (Type::Rain, self.rain),
+            (Type::SumRain, self.sum_rain_1),
+            (Type::SumRain, self.sum_rain_24),
+            (Type::WindStrength, self.wind_strength.map(|x| x as f64)),
+            (Type::WindStrength, self.max_wind_str.map(|x| x as f64)),
+            (Type::GustStrength, self.gust_strength.map(|x| x as f64)),
+            (Type::WindAngle, self.wind_angle.map(|x| x as f64)),
+            (Type::WindAngle, self.max_wind_angle.map(|x| x as f64)),
+            (Type::GustAngle, self.gust_angle.map(|x| x as f64)),
+        ]
+    }
+
+    pub fn outliers(&self, bounds: &SanityBounds) -> Vec<Outlier> {
+        self.readings()
+            .into_iter()
+            .filter_map(|(t, v)| match v {
+                Some(value) if !bounds.is_plausible(&t, value) => Some(Outlier {
+                    timestamp: self.time_utc.map(|x| x.epoch_seconds()),
+                    measurement_type: t,
+                    value,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Removes the values outside of `bounds`.
+    pub fn sanitized(&self, bounds: &SanityBounds) -> DashboardData {
+        DashboardData {
+            temperature: bounds.check(&Type::Temperature, self.temperature),
+            min_temp: bounds.check(&Type::MinTemp, self.min_temp),
+            max_temp: bounds.check(&Type::MaxTemp, self.max_temp),
+            humidity: bounds.check_integer(&Type::Humidity, self.humidity),
+            co2: bounds.check_integer(&Type::CO2, self.co2),
+            noise: bounds.check_integer(&Type::Noise, self.noise),
+            pressure: bounds.check(&Type::Pressure, self.pressure),
+            absolute_pressure: bounds.check(&Type::Pressure, self.absolute_pressure),
+            rain: bounds.check(&Type::Rain, self.rain),
+            sum_rain_1: bounds.check(&Type::SumRain, self.sum_rain_1),
+            sum_rain_24: bounds.check(&Type::SumRain, self.sum_rain_24),
+            wind_strength: bounds.check_integer(&Type::WindStrength, self.wind_strength),
+            max_wind_str: bounds.check_integer(&Type::WindStrength, self.max_wind_str),
+            gust_strength: bounds.check_integer(&Type::GustStrength, self.gust_strength),
+            wind_angle: bounds.check_integer(&Type::WindAngle, self.wind_angle),
+            max_wind_angle: bounds.check_integer(&Type::WindAngle, self.max_wind_angle),
+            gust_angle: bounds.check_integer(&Type::GustAngle, self.gust_angle),
+            ..self.clone()
+        }
+    }
+}
+
+impl Room {
+    /// Removes the measured temperature if it is outside of `bounds`.
+    pub fn sanitized(&self, bounds: &SanityBounds) -> Room {
+        Room {
+            therm_measured_temperature: bounds.check(&Type::Temperature, self.therm_measured_temperature),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod sanity {
+        use super::*;
+
+        #[test]
+        fn null_bogus_values() {
+            let series = TimeSeries::new(
+                &[Type::Temperature, Type::Humidity],
+                vec![
+                    (100, vec![Some(20.5), Some(45.0)]),
+                    (200, vec![Some(-3276.8), Some(46.0)]),
+                    (300, vec![Some(f64::NAN), Some(146.0)]),
+                ],
+            );
+            let bounds = SanityBounds::default();
+
+            let outliers = series.outliers(&bounds);
+            assert_eq!(outliers.len(), 3);
+            assert_eq!(
+                outliers[0],
+                Outlier {
+                    timestamp: Some(200),
+                    measurement_type: Type::Temperature,
+                    value: -3276.8,
+                }
+            );
+
+            let sanitized = series.sanitized(&bounds);
+            assert_eq!(sanitized.temperature(), vec![(100, 20.5)]);
+            assert_eq!(sanitized.humidity(), vec![(100, 45.0), (200, 46.0)]);
+
+            let strict = bounds.bound(&[Type::Temperature], 21.0, 30.0);
+            assert!(series.sanitized(&strict).temperature().is_empty());
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use super::module_type::ModuleType;
+use crate::errors::{NetatmoError, Result};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// The OAuth scopes an access token needs to be granted for the respective calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Scope {
+    ReadStation,
+    ReadThermostat,
+    WriteThermostat,
+    ReadCamera,
+    WriteCamera,
+    AccessCamera,
+    ReadPresence,
+    WritePresence,
+    AccessPresence,
+    ReadHomecoach,
+    ReadSmokedetector,
+    ReadCarbonmonoxidedetector,
+    ReadDoorbell,
+    AccessDoorbell,
+    ReadMagellan,
+    WriteMagellan,
+    ReadBubendorff,
+    WriteBubendorff,
+    ReadSmarther,
+    WriteSmarther,
+    ReadMx,
+    WriteMx,
+}
+
+impl Scope {
+    /// Joins scopes with spaces, as expected by the authorization endpoints.
+    pub fn join(scopes: &[Scope]) -> String {
+        scopes.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" ")
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Scope::ReadStation => "read_station",
+            Scope::ReadThermostat => "read_thermostat",
+            Scope::WriteThermostat => "write_thermostat",
+            Scope::ReadCamera => "read_camera",
+            Scope::WriteCamera => "write_camera",
+            Scope::AccessCamera => "access_camera",
+            Scope::ReadPresence => "read_presence",
+            Scope::WritePresence => "write_presence",
+            Scope::AccessPresence => "access_presence",
+            Scope::ReadHomecoach => "read_homecoach",
+            Scope::ReadSmokedetector => "read_smokedetector",
+            Scope::ReadCarbonmonoxidedetector => "read_carbonmonoxidedetector",
+            Scope::ReadDoorbell => "read_doorbell",
+            Scope::AccessDoorbell => "access_doorbell",
+            Scope::ReadMagellan => "read_magellan",
+            Scope::WriteMagellan => "write_magellan",
+            Scope::ReadBubendorff => "read_bubendorff",
+            Scope::WriteBubendorff => "write_bubendorff",
+            Scope::ReadSmarther => "read_smarther",
+            Scope::WriteSmarther => "write_smarther",
+            Scope::ReadMx => "read_mx",
+            Scope::WriteMx => "write_mx",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Scope {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "read_station" => Ok(Scope::ReadStation),
+            "read_thermostat" => Ok(Scope::ReadThermostat),
+            "write_thermostat" => Ok(Scope::WriteThermostat),
+            "read_camera" => Ok(Scope::ReadCamera),
+            "write_camera" => Ok(Scope::WriteCamera),
+            "access_camera" => Ok(Scope::AccessCamera),
+            "read_presence" => Ok(Scope::ReadPresence),
+            "write_presence" => Ok(Scope::WritePresence),
+            "access_presence" => Ok(Scope::AccessPresence),
+            "read_homecoach" => Ok(Scope::ReadHomecoach),
+            "read_smokedetector" => Ok(Scope::ReadSmokedetector),
+            "read_carbonmonoxidedetector" => Ok(Scope::ReadCarbonmonoxidedetector),
+            "read_doorbell" => Ok(Scope::ReadDoorbell),
+            "access_doorbell" => Ok(Scope::AccessDoorbell),
+            "read_magellan" => Ok(Scope::ReadMagellan),
+            "write_magellan" => Ok(Scope::WriteMagellan),
+            "read_bubendorff" => Ok(Scope::ReadBubendorff),
+            "write_bubendorff" => Ok(Scope::WriteBubendorff),
+            "read_smarther" => Ok(Scope::ReadSmarther),
+            "write_smarther" => Ok(Scope::WriteSmarther),
+            "read_mx" => Ok(Scope::ReadMx),
+            "write_mx" => Ok(Scope::WriteMx),
+            _ => Err(NetatmoError::invalid_parameter(
+                "scope",
+                &format!("unknown value '{}'", s),
+            )),
+        }
+    }
+}
+
+impl ModuleType {
+    /// The scope needed to read the status of modules of this type.
+    pub fn read_scope(&self) -> Option<Scope> {
+        self.scopes().0
+    }
+
+    /// The scope needed to control modules of this type, `None` for modules that can't be controlled.
+    pub fn write_scope(&self) -> Option<Scope> {
+        self.scopes().1
+    }
+
+    fn scopes(&self) -> (Option<Scope>, Option<Scope>) {
+        match self {
+            ModuleType::NAMain
+            | ModuleType::NAModule1
+            | ModuleType::NAModule2
+            | ModuleType::NAModule3
+            | ModuleType::NAModule4 => (Some(Scope::ReadStation), None),
+            ModuleType::NHC => (Some(Scope::ReadHomecoach), None),
+            ModuleType::NAPlug | ModuleType::NATherm1 | ModuleType::NRV | ModuleType::OTH | ModuleType::OTM => {
+                (Some(Scope::ReadThermostat), Some(Scope::WriteThermostat))
+            }
+            ModuleType::BNS => (Some(Scope::ReadSmarther), Some(Scope::WriteSmarther)),
+            ModuleType::NACamera | ModuleType::NACamDoorTag | ModuleType::NIS => {
+                (Some(Scope::ReadCamera), Some(Scope::WriteCamera))
+            }
+            ModuleType::NOC => (Some(Scope::ReadPresence), Some(Scope::WritePresence)),
+            ModuleType::NDB => (Some(Scope::ReadDoorbell), None),
+            ModuleType::NSD => (Some(Scope::ReadSmokedetector), None),
+            ModuleType::NCO => (Some(Scope::ReadCarbonmonoxidedetector), None),
+            ModuleType::NBR | ModuleType::NBO | ModuleType::NBS => {
+                (Some(Scope::ReadBubendorff), Some(Scope::WriteBubendorff))
+            }
+            ModuleType::NMG | ModuleType::NMH | ModuleType::NMR => (Some(Scope::ReadMx), Some(Scope::WriteMx)),
+            ModuleType::NLG
+            | ModuleType::NLP
+            | ModuleType::NLPM
+            | ModuleType::NLPT
+            | ModuleType::NLPC
+            | ModuleType::NLE
+            | ModuleType::NLV
+            | ModuleType::NLLV
+            | ModuleType::NLF
+            | ModuleType::NLFN
+            | ModuleType::NLM
+            | ModuleType::NLT
+            | ModuleType::NLPO
+            | ModuleType::NLC => (Some(Scope::ReadMagellan), Some(Scope::WriteMagellan)),
+            ModuleType::DTG | ModuleType::Other(_) => (None, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::assert_round_trip;
+
+    mod scope {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            assert_round_trip!(
+                Scope::ReadStation,
+                Scope::ReadThermostat,
+                Scope::WriteThermostat,
+                Scope::ReadCamera,
+                Scope::WriteCamera,
+                Scope::AccessCamera,
+                Scope::ReadPresence,
+                Scope::WritePresence,
+                Scope::AccessPresence,
+                Scope::ReadHomecoach,
+                Scope::ReadSmokedetector,
+                Scope::ReadCarbonmonoxidedetector,
+                Scope::ReadDoorbell,
+                Scope::AccessDoorbell,
+                Scope::ReadMagellan,
+                Scope::WriteMagellan,
+                Scope::ReadBubendorff,
+                Scope::WriteBubendorff,
+                Scope::ReadSmarther,
+                Scope::WriteSmarther,
+                Scope::ReadMx,
+                Scope::WriteMx,
+            );
+            assert_eq!(
+                Scope::join(&[Scope::ReadStation, Scope::ReadHomecoach]),
+                "read_station read_homecoach"
+            );
+        }
+
+        #[test]
+        fn reject_unknown() {
+            assert!(matches!(
+                "read_everything".parse::<Scope>(),
+                Err(NetatmoError::InvalidParameter { name, .. }) if name == "scope"
+            ));
+        }
+
+        #[test]
+        fn module_type_scopes() {
+            let cases = [
+                (ModuleType::NAMain, Some(Scope::ReadStation), None),
+                (ModuleType::NHC, Some(Scope::ReadHomecoach), None),
+                (
+                    ModuleType::NRV,
+                    Some(Scope::ReadThermostat),
+                    Some(Scope::WriteThermostat),
+                ),
+                (ModuleType::BNS, Some(Scope::ReadSmarther), Some(Scope::WriteSmarther)),
+                (ModuleType::NACamera, Some(Scope::ReadCamera), Some(Scope::WriteCamera)),
+                (ModuleType::NOC, Some(Scope::ReadPresence), Some(Scope::WritePresence)),
+                (ModuleType::NDB, Some(Scope::ReadDoorbell), None),
+                (ModuleType::NSD, Some(Scope::ReadSmokedetector), None),
+                (ModuleType::NCO, Some(Scope::ReadCarbonmonoxidedetector), None),
+                (
+                    ModuleType::NBR,
+                    Some(Scope::ReadBubendorff),
+                    Some(Scope::WriteBubendorff),
+                ),
+                (
+                    ModuleType::NBO,
+                    Some(Scope::ReadBubendorff),
+                    Some(Scope::WriteBubendorff),
+                ),
+                (
+                    ModuleType::NBS,
+                    Some(Scope::ReadBubendorff),
+                    Some(Scope::WriteBubendorff),
+                ),
+                (ModuleType::NMG, Some(Scope::ReadMx), Some(Scope::WriteMx)),
+                (ModuleType::NLV, Some(Scope::ReadMagellan), Some(Scope::WriteMagellan)),
+                (ModuleType::DTG, None, None),
+                (ModuleType::Other("NXYZ".to_string()), None, None),
+            ];
+            for (module_type, read, write) in cases {
+                assert_eq!(module_type.read_scope(), read, "{}", module_type);
+                assert_eq!(module_type.write_scope(), write, "{}", module_type);
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::{
+    ids::{HomeId, PersonId},
+    timestamp::{epoch, Epoch},
+};
+use crate::{client::NetatmoClient, errors::Result};
+use serde::{Deserialize, Serialize};
+
+/// Sent as JSON like `setpersonshome`'s body, whose list of persons doesn't fit a form.
+#[derive(Serialize, Debug)]
+struct SetPersonsAwayBody<'a> {
+    home_id: &'a HomeId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    person_id: Option<&'a PersonId>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SetPersonsAwayResponse {
+    pub status: String,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+}
+
+/// Marks `person_id` as away, or the whole home if no person is given.
+// cf. https://dev.netatmo.com/apidocumentation/security#setpersonsaway
+pub async fn set_persons_away(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    person_id: Option<&PersonId>,
+) -> Result<SetPersonsAwayResponse> {
+    let body = SetPersonsAwayBody { home_id, person_id };
+
+    client
+        .call_json("set_persons_away", "https://api.netatmo.com/api/setpersonsaway", &body)
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod set_persons_away {
+        use super::*;
+
+        #[test]
+        fn serialize_body() {
+            let home_id = HomeId::new("home");
+            let person_id = PersonId::new("person");
+
+            let person = serde_json::to_string(&SetPersonsAwayBody {
+                home_id: &home_id,
+                person_id: Some(&person_id),
+            })
+            .unwrap();
+            let everyone = serde_json::to_string(&SetPersonsAwayBody {
+                home_id: &home_id,
+                person_id: None,
+            })
+            .unwrap();
+
+            assert_eq!(person, r#"{"home_id":"home","person_id":"person"}"#);
+            assert_eq!(everyone, r#"{"home_id":"home"}"#);
+        }
+    }
+}
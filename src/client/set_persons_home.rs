@@ -0,0 +1,56 @@
+use super::{
+    ids::{HomeId, PersonId},
+    timestamp::{epoch, Epoch},
+};
+use crate::{client::NetatmoClient, errors::Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Debug)]
+struct SetPersonsHomeBody<'a> {
+    home_id: &'a HomeId,
+    person_ids: &'a [PersonId],
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SetPersonsHomeResponse {
+    pub status: String,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/security#setpersonshome
+pub async fn set_persons_home(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    person_ids: &[PersonId],
+) -> Result<SetPersonsHomeResponse> {
+    let body = SetPersonsHomeBody { home_id, person_ids };
+
+    client
+        .call_json("set_persons_home", "https://api.netatmo.com/api/setpersonshome", &body)
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod set_persons_home {
+        use super::*;
+
+        #[test]
+        fn serialize_body() {
+            let body = SetPersonsHomeBody {
+                home_id: &HomeId::new("home"),
+                person_ids: &[PersonId::new("alice"), PersonId::new("bob")],
+            };
+
+            let json = serde_json::to_string(&body).unwrap();
+
+            assert_eq!(json, r#"{"home_id":"home","person_ids":["alice","bob"]}"#);
+        }
+    }
+}
@@ -1,24 +1,37 @@
+use super::{
+    ids::{HomeId, RoomId},
+    timestamp::{epoch, Epoch, Timestamp},
+};
 use crate::{
     client::NetatmoClient,
     errors::{NetatmoError, Result},
 };
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fmt, str::FromStr};
+use std::{collections::HashMap, fmt, future::Future, str::FromStr};
+
+const MAX_CONCURRENT_REQUESTS: usize = 4;
 
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct SetRoomThermpointParameters {
-    home_id: String,
-    room_id: String,
+    home_id: HomeId,
+    room_id: RoomId,
     mode: Mode,
     temp: Option<f32>,
-    endtime: Option<usize>,
+    endtime: Option<i64>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(try_from = "String", into = "String")]
+#[non_exhaustive]
 pub enum Mode {
     #[default]
     Manual,
     Max,
     Home,
+    Off,
+    FrostGuard,
 }
 
 impl fmt::Display for Mode {
@@ -27,6 +40,8 @@ impl fmt::Display for Mode {
             Mode::Manual => "manual",
             Mode::Max => "max",
             Mode::Home => "home",
+            Mode::Off => "off",
+            Mode::FrostGuard => "hg",
         };
         write!(f, "{}", s)
     }
@@ -40,22 +55,50 @@ impl FromStr for Mode {
             "manual" => Ok(Mode::Manual),
             "max" => Ok(Mode::Max),
             "home" => Ok(Mode::Home),
+            "off" => Ok(Mode::Off),
+            "hg" => Ok(Mode::FrostGuard),
             _ => Err(NetatmoError::JsonDeserializationFailed),
         }
     }
 }
 
+impl TryFrom<String> for Mode {
+    type Error = NetatmoError;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<Mode> for String {
+    fn from(m: Mode) -> Self {
+        m.to_string()
+    }
+}
+
 impl SetRoomThermpointParameters {
-    pub fn new(home_id: &str, room_id: &str, mode: Mode) -> Self {
+    pub fn new(home_id: &HomeId, room_id: &RoomId, mode: Mode) -> Self {
         SetRoomThermpointParameters {
-            home_id: home_id.to_string(),
-            room_id: room_id.to_string(),
+            home_id: home_id.clone(),
+            room_id: room_id.clone(),
             mode,
             temp: None,
             endtime: None,
         }
     }
 
+    pub fn home_id(&self) -> &HomeId {
+        &self.home_id
+    }
+
+    pub fn room_id(&self) -> &RoomId {
+        &self.room_id
+    }
+
+    pub fn mode(&self) -> &Mode {
+        &self.mode
+    }
+
     pub fn temp(self, temp: f32) -> Self {
         SetRoomThermpointParameters {
             temp: Some(temp),
@@ -63,36 +106,60 @@ impl SetRoomThermpointParameters {
         }
     }
 
-    pub fn date_end(self, date_end: usize) -> Self {
+    pub fn date_end(self, date_end: i64) -> Self {
         SetRoomThermpointParameters {
             endtime: Some(date_end),
             ..self
         }
     }
+
+    /// Like `date_end`, from e.g. a `SystemTime` or, with the `chrono` feature, a `DateTime`.
+    pub fn date_end_at<T: Timestamp>(self, date_end: T) -> Self {
+        self.date_end(date_end.epoch_seconds())
+    }
+
+    /// Checks the combination of parameters the API would reject, which `set_room_thermpoint` does before calling it.
+    pub fn validate(&self) -> Result<()> {
+        if self.mode == Mode::Manual && self.temp.is_none() {
+            return Err(NetatmoError::invalid_parameter("temp", "is required in manual mode"));
+        }
+        if self.endtime.is_some() && !matches!(self.mode, Mode::Manual | Mode::Max) {
+            return Err(NetatmoError::invalid_parameter(
+                "endtime",
+                "can only be set in manual or max mode",
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 #[allow(clippy::implicit_hasher)]
-impl From<&SetRoomThermpointParameters> for HashMap<String, String> {
-    fn from(p: &SetRoomThermpointParameters) -> HashMap<String, String> {
+impl From<&SetRoomThermpointParameters> for HashMap<&'static str, String> {
+    fn from(p: &SetRoomThermpointParameters) -> HashMap<&'static str, String> {
         let mut map = HashMap::default();
-        map.insert("home_id".to_string(), p.home_id.to_string());
-        map.insert("room_id".to_string(), p.room_id.to_string());
-        map.insert("mode".to_string(), p.mode.to_string());
+        map.insert("home_id", p.home_id.to_string());
+        map.insert("room_id", p.room_id.to_string());
+        map.insert("mode", p.mode.to_string());
         if let Some(temp) = p.temp {
-            map.insert("temp".to_string(), temp.to_string());
+            map.insert("temp", temp.to_string());
         }
         if let Some(endtime) = p.endtime {
-            map.insert("endtime".to_string(), endtime.to_string());
+            map.insert("endtime", endtime.to_string());
         }
 
         map
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub struct SetRoomThermpointResponse {
     pub status: String,
-    pub time_server: usize,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
 }
 
 // cf. https://dev.netatmo.com/resources/technical/reference/energy/setroomthermpoint
@@ -100,14 +167,190 @@ pub async fn set_room_thermpoint(
     client: &NetatmoClient,
     parameters: &SetRoomThermpointParameters,
 ) -> Result<SetRoomThermpointResponse> {
-    let params: HashMap<String, String> = parameters.into();
-    let mut params = params.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    parameters.validate()?;
+    let params: HashMap<&str, String> = parameters.into();
 
     client
         .call(
             "set_room_thermpoint",
             "https://api.netatmo.com/api/setroomthermpoint",
-            &mut params,
+            &params,
         )
         .await
 }
+
+/// Turns the room's valves off, e.g. for unused guest rooms.
+pub async fn room_off(client: &NetatmoClient, home_id: &HomeId, room_id: &RoomId) -> Result<SetRoomThermpointResponse> {
+    let parameters = SetRoomThermpointParameters::new(home_id, room_id, Mode::Off);
+    set_room_thermpoint(client, &parameters).await
+}
+
+/// Keeps the room at the home's frost guard temperature.
+pub async fn room_frost_guard(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    room_id: &RoomId,
+) -> Result<SetRoomThermpointResponse> {
+    let parameters = SetRoomThermpointParameters::new(home_id, room_id, Mode::FrostGuard);
+    set_room_thermpoint(client, &parameters).await
+}
+
+/// Sets the thermpoint of several rooms of the same home, running at most `MAX_CONCURRENT_REQUESTS` calls at once.
+///
+/// A failure for one room doesn't abort the others; the result of every room is returned in the order of `rooms`, so a
+/// room listed twice gets two results. The temperatures are only sent with `Mode::Manual`, other modes ignore them.
+pub async fn set_rooms_thermpoint(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    rooms: &[(RoomId, f32)],
+    mode: Mode,
+) -> Vec<(RoomId, Result<SetRoomThermpointResponse>)> {
+    for_each_room(rooms, |room_id, temp| {
+        let parameters = room_parameters(home_id, room_id, &mode, temp);
+        async move { set_room_thermpoint(client, &parameters).await }
+    })
+    .await
+}
+
+fn room_parameters(home_id: &HomeId, room_id: &RoomId, mode: &Mode, temp: f32) -> SetRoomThermpointParameters {
+    let parameters = SetRoomThermpointParameters::new(home_id, room_id, mode.clone());
+    match mode {
+        Mode::Manual => parameters.temp(temp),
+        _ => parameters,
+    }
+}
+
+async fn for_each_room<'a, F, Fut, T>(rooms: &'a [(RoomId, f32)], f: F) -> Vec<(RoomId, T)>
+where
+    F: Fn(&'a RoomId, f32) -> Fut,
+    Fut: Future<Output = T>,
+{
+    stream::iter(rooms)
+        .map(|(room_id, temp)| {
+            let res = f(room_id, *temp);
+            async move { (room_id.clone(), res.await) }
+        })
+        .buffered(MAX_CONCURRENT_REQUESTS)
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::assert_round_trip;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    mod set_room_thermpoint {
+        use super::*;
+
+        #[test]
+        fn frost_guard_parameters() {
+            let parameters =
+                SetRoomThermpointParameters::new(&HomeId::new("home"), &RoomId::new("room"), Mode::FrostGuard);
+
+            let map: HashMap<&str, String> = (&parameters).into();
+
+            assert_eq!(map.get("mode"), Some(&"hg".to_string()));
+            assert_eq!(Mode::from_str("hg"), Ok(Mode::FrostGuard));
+        }
+
+        #[test]
+        fn mode_round_trip() {
+            assert_round_trip!(Mode::Manual, Mode::Max, Mode::Home, Mode::Off, Mode::FrostGuard);
+        }
+
+        #[test]
+        fn validate() {
+            assert!(
+                SetRoomThermpointParameters::new(&HomeId::new("home"), &RoomId::new("room"), Mode::Manual)
+                    .temp(19.5)
+                    .date_end(1689865621)
+                    .validate()
+                    .is_ok()
+            );
+            assert!(matches!(
+                SetRoomThermpointParameters::new(&HomeId::new("home"), &RoomId::new("room"), Mode::Manual).validate(),
+                Err(NetatmoError::InvalidParameter { name, .. }) if name == "temp"
+            ));
+            assert!(matches!(
+                SetRoomThermpointParameters::new(&HomeId::new("home"), &RoomId::new("room"), Mode::Home)
+                    .date_end(1689865621)
+                    .validate(),
+                Err(NetatmoError::InvalidParameter { name, .. }) if name == "endtime"
+            ));
+        }
+
+        #[test]
+        fn date_end_at() {
+            let parameters =
+                SetRoomThermpointParameters::new(&HomeId::new("home"), &RoomId::new("room"), Mode::Manual).temp(19.5);
+
+            assert_eq!(
+                parameters
+                    .clone()
+                    .date_end_at(UNIX_EPOCH + Duration::from_secs(1689865621)),
+                parameters.date_end(1689865621)
+            );
+        }
+    }
+
+    mod set_rooms_thermpoint {
+        use super::*;
+        use std::cell::Cell;
+
+        #[test]
+        fn temp_only_for_manual() {
+            let (home_id, room_id) = (HomeId::new("home"), RoomId::new("room"));
+
+            let manual: HashMap<&str, String> = (&room_parameters(&home_id, &room_id, &Mode::Manual, 19.5)).into();
+            let home: HashMap<&str, String> = (&room_parameters(&home_id, &room_id, &Mode::Home, 19.5)).into();
+
+            assert_eq!(manual.get("temp"), Some(&"19.5".to_string()));
+            assert_eq!(home.get("temp"), None);
+            assert_eq!(home.get("mode"), Some(&"home".to_string()));
+        }
+
+        #[tokio::test]
+        async fn results_in_input_order() {
+            let rooms = [
+                (RoomId::new("1"), 19.0),
+                (RoomId::new("2"), 20.0),
+                (RoomId::new("1"), 21.0),
+            ];
+
+            let results = for_each_room(&rooms, |_, temp| async move {
+                tokio::time::sleep(Duration::from_millis(30 - temp as u64)).await;
+                temp
+            })
+            .await;
+
+            assert_eq!(
+                results,
+                [
+                    (RoomId::new("1"), 19.0),
+                    (RoomId::new("2"), 20.0),
+                    (RoomId::new("1"), 21.0)
+                ]
+            );
+        }
+
+        #[tokio::test]
+        async fn bounded_concurrency() {
+            let rooms: Vec<_> = (0..10).map(|x| (RoomId::new(&x.to_string()), 19.0)).collect();
+            let running = Cell::new(0);
+            let max_running = Cell::new(0);
+
+            let results = for_each_room(&rooms, |_, _| async {
+                running.set(running.get() + 1);
+                max_running.set(max_running.get().max(running.get()));
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                running.set(running.get() - 1);
+            })
+            .await;
+
+            assert_eq!(results.len(), 10);
+            assert_eq!(max_running.get(), MAX_CONCURRENT_REQUESTS);
+        }
+    }
+}
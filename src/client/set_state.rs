@@ -0,0 +1,627 @@
+use super::{
+    ids::{HomeId, ModuleId, RoomId},
+    set_room_thermpoint::Mode,
+    timestamp::{epoch, Epoch},
+};
+use crate::{
+    client::NetatmoClient,
+    errors::{NetatmoError, Result},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SetStateParameters {
+    home: SetStateHome,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct SetStateHome {
+    id: HomeId,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    modules: Vec<ModuleState>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    rooms: Vec<RoomState>,
+}
+
+impl SetStateParameters {
+    pub fn new(home_id: &HomeId, modules: &[ModuleState]) -> Self {
+        SetStateParameters {
+            home: SetStateHome {
+                id: home_id.clone(),
+                modules: modules.to_vec(),
+                rooms: vec![],
+            },
+        }
+    }
+
+    pub fn home_id(&self) -> &HomeId {
+        &self.home.id
+    }
+
+    /// Checks that there is something to set, which `set_state` does before calling the API.
+    pub fn validate(&self) -> Result<()> {
+        if self.home.modules.is_empty() && self.home.rooms.is_empty() {
+            return Err(NetatmoError::invalid_parameter(
+                "home",
+                "has neither modules nor rooms to set",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn module_ids(&self) -> Vec<&ModuleId> {
+        self.home.modules.iter().map(|x| &x.id).collect()
+    }
+
+    pub fn rooms(self, rooms: &[RoomState]) -> Self {
+        SetStateParameters {
+            home: SetStateHome {
+                rooms: rooms.to_vec(),
+                ..self.home
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModuleState {
+    id: ModuleId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bridge: Option<ModuleId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    monitoring: Option<Monitoring>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    floodlight: Option<FloodlightMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    on: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_position: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    brightness: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scenario: Option<String>,
+}
+
+impl ModuleState {
+    pub fn new(module_id: &ModuleId) -> Self {
+        ModuleState {
+            id: module_id.clone(),
+            bridge: None,
+            monitoring: None,
+            floodlight: None,
+            on: None,
+            target_position: None,
+            brightness: None,
+            scenario: None,
+        }
+    }
+
+    /// The gateway the module is paired with, required for modules that don't connect to the cloud themselves.
+    pub fn bridge(self, bridge: &ModuleId) -> Self {
+        ModuleState {
+            bridge: Some(bridge.clone()),
+            ..self
+        }
+    }
+
+    pub fn monitoring(self, monitoring: Monitoring) -> Self {
+        ModuleState {
+            monitoring: Some(monitoring),
+            ..self
+        }
+    }
+
+    pub fn floodlight(self, floodlight: FloodlightMode) -> Self {
+        ModuleState {
+            floodlight: Some(floodlight),
+            ..self
+        }
+    }
+
+    pub fn on(self, on: bool) -> Self {
+        ModuleState { on: Some(on), ..self }
+    }
+
+    pub fn target_position(self, position: ShutterPosition) -> Self {
+        ModuleState {
+            target_position: Some(position.into()),
+            ..self
+        }
+    }
+
+    /// Brightness of a dimmer from 0 to 100.
+    pub fn brightness(self, brightness: u8) -> Self {
+        ModuleState {
+            brightness: Some(brightness.min(100)),
+            ..self
+        }
+    }
+
+    /// Launches a scenario, set on the gateway the scenario's modules are paired with.
+    pub fn scenario(self, scenario_id: &str) -> Self {
+        ModuleState {
+            scenario: Some(scenario_id.to_string()),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoomState {
+    id: RoomId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    therm_setpoint_mode: Option<Mode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    therm_setpoint_temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    therm_setpoint_end_time: Option<i64>,
+}
+
+impl RoomState {
+    pub fn new(room_id: &RoomId) -> Self {
+        RoomState {
+            id: room_id.clone(),
+            therm_setpoint_mode: None,
+            therm_setpoint_temperature: None,
+            therm_setpoint_end_time: None,
+        }
+    }
+
+    pub fn mode(self, mode: Mode) -> Self {
+        RoomState {
+            therm_setpoint_mode: Some(mode),
+            ..self
+        }
+    }
+
+    pub fn temperature(self, temperature: f32) -> Self {
+        RoomState {
+            therm_setpoint_temperature: Some(temperature),
+            ..self
+        }
+    }
+
+    pub fn end_time(self, end_time: i64) -> Self {
+        RoomState {
+            therm_setpoint_end_time: Some(end_time),
+            ..self
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Monitoring {
+    #[default]
+    On,
+    Off,
+}
+
+impl fmt::Display for Monitoring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Monitoring::On => "on",
+            Monitoring::Off => "off",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Monitoring {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "on" => Ok(Monitoring::On),
+            "off" => Ok(Monitoring::Off),
+            _ => Err(NetatmoError::invalid_parameter(
+                "monitoring",
+                &format!("unknown value '{}'", s),
+            )),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum FloodlightMode {
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl fmt::Display for FloodlightMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            FloodlightMode::Auto => "auto",
+            FloodlightMode::On => "on",
+            FloodlightMode::Off => "off",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for FloodlightMode {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "auto" => Ok(FloodlightMode::Auto),
+            "on" => Ok(FloodlightMode::On),
+            "off" => Ok(FloodlightMode::Off),
+            _ => Err(NetatmoError::invalid_parameter(
+                "floodlight",
+                &format!("unknown value '{}'", s),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutterPosition {
+    Open,
+    Close,
+    Stop,
+    /// From 0 (closed) to 100 (open), for shutters supporting intermediate positions
+    Percent(u8),
+}
+
+impl From<ShutterPosition> for i64 {
+    fn from(position: ShutterPosition) -> i64 {
+        match position {
+            ShutterPosition::Open => 100,
+            ShutterPosition::Close => 0,
+            ShutterPosition::Stop => -1,
+            ShutterPosition::Percent(x) => i64::from(x.min(100)),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SetStateResponse {
+    pub status: String,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+    pub body: Option<SetStateBody>,
+}
+
+#[derive(Default, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SetStateBody {
+    #[serde(default)]
+    pub errors: Vec<SetStateError>,
+}
+
+/// A module whose state change failed while the others of the same request succeeded.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SetStateError {
+    pub code: i64,
+    pub id: ModuleId,
+}
+
+impl SetStateResponse {
+    /// The outcome for each of the given modules, which succeeded unless the API reported an error for them.
+    pub fn module_results(
+        &self,
+        module_ids: &[&ModuleId],
+    ) -> HashMap<ModuleId, std::result::Result<(), SetStateError>> {
+        let errors = self.body.as_ref().map(|x| x.errors.as_slice()).unwrap_or_default();
+        module_ids
+            .iter()
+            .map(|id| {
+                let result = match errors.iter().find(|x| x.id == **id) {
+                    Some(error) => Err(error.clone()),
+                    None => Ok(()),
+                };
+                ((*id).clone(), result)
+            })
+            .collect()
+    }
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/control#setstate
+pub async fn set_state(client: &NetatmoClient, parameters: &SetStateParameters) -> Result<SetStateResponse> {
+    parameters.validate()?;
+    client
+        .call_json("set_state", "https://api.netatmo.com/api/setstate", parameters)
+        .await
+}
+
+/// Changes the state of several modules in one request, returning the outcome for each of them.
+pub async fn set_states(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    modules: &[ModuleState],
+) -> Result<HashMap<ModuleId, std::result::Result<(), SetStateError>>> {
+    let parameters = SetStateParameters::new(home_id, modules);
+    let response = set_state(client, &parameters).await?;
+
+    Ok(response.module_results(&parameters.module_ids()))
+}
+
+/// Switches a camera's monitoring on or off, e.g. to disable an indoor camera while someone is home.
+pub async fn set_camera_monitoring(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    module_id: &ModuleId,
+    monitoring: Monitoring,
+) -> Result<SetStateResponse> {
+    let parameters = SetStateParameters::new(home_id, &[ModuleState::new(module_id).monitoring(monitoring)]);
+    set_state(client, &parameters).await
+}
+
+/// Sets the floodlight of a Presence camera.
+pub async fn set_floodlight(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    module_id: &ModuleId,
+    floodlight: FloodlightMode,
+) -> Result<SetStateResponse> {
+    let parameters = SetStateParameters::new(home_id, &[ModuleState::new(module_id).floodlight(floodlight)]);
+    set_state(client, &parameters).await
+}
+
+/// Switches a smart plug (NLP) paired with the gateway `bridge` on or off.
+pub async fn set_plug(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    module_id: &ModuleId,
+    bridge: &ModuleId,
+    on: bool,
+) -> Result<SetStateResponse> {
+    set_state(client, &switch_parameters(home_id, module_id, bridge, on)).await
+}
+
+/// Plugs, lights and light micromodules are all switched with `on`.
+fn switch_parameters(home_id: &HomeId, module_id: &ModuleId, bridge: &ModuleId, on: bool) -> SetStateParameters {
+    SetStateParameters::new(home_id, &[ModuleState::new(module_id).bridge(bridge).on(on)])
+}
+
+/// Opens, closes or stops a shutter (NLV, NLLV, NBR, NBO, NBS) paired with the gateway `bridge`, or moves it to a
+/// position. Legrand shutters need the `write_magellan` scope, Bubendorff ones `write_bubendorff`.
+pub async fn set_shutter(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    module_id: &ModuleId,
+    bridge: &ModuleId,
+    position: ShutterPosition,
+) -> Result<SetStateResponse> {
+    set_state(client, &shutter_parameters(home_id, module_id, bridge, position)).await
+}
+
+fn shutter_parameters(
+    home_id: &HomeId,
+    module_id: &ModuleId,
+    bridge: &ModuleId,
+    position: ShutterPosition,
+) -> SetStateParameters {
+    SetStateParameters::new(
+        home_id,
+        &[ModuleState::new(module_id).bridge(bridge).target_position(position)],
+    )
+}
+
+/// Switches a light or light micromodule (NLF, NLFN, NLM) paired with the gateway `bridge` on or off.
+/// Sends the same request as [`set_plug`], named for the modules it is meant for.
+pub async fn set_light(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    module_id: &ModuleId,
+    bridge: &ModuleId,
+    on: bool,
+) -> Result<SetStateResponse> {
+    set_state(client, &switch_parameters(home_id, module_id, bridge, on)).await
+}
+
+/// Switches a dimmer (NLF, NLFN) on at the given brightness from 0 to 100.
+pub async fn set_brightness(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    module_id: &ModuleId,
+    bridge: &ModuleId,
+    brightness: u8,
+) -> Result<SetStateResponse> {
+    set_state(client, &brightness_parameters(home_id, module_id, bridge, brightness)).await
+}
+
+fn brightness_parameters(
+    home_id: &HomeId,
+    module_id: &ModuleId,
+    bridge: &ModuleId,
+    brightness: u8,
+) -> SetStateParameters {
+    SetStateParameters::new(
+        home_id,
+        &[ModuleState::new(module_id)
+            .bridge(bridge)
+            .on(true)
+            .brightness(brightness)],
+    )
+}
+
+/// Launches a scenario listed by `get_scenarios` on the gateway (NLG) `bridge_id`.
+pub async fn launch_scenario(
+    client: &NetatmoClient,
+    home_id: &HomeId,
+    bridge_id: &ModuleId,
+    scenario_id: &str,
+) -> Result<SetStateResponse> {
+    set_state(client, &scenario_parameters(home_id, bridge_id, scenario_id)).await
+}
+
+fn scenario_parameters(home_id: &HomeId, bridge_id: &ModuleId, scenario_id: &str) -> SetStateParameters {
+    SetStateParameters::new(home_id, &[ModuleState::new(bridge_id).scenario(scenario_id)])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::assert_round_trip;
+
+    mod set_state {
+        use super::*;
+
+        #[test]
+        fn serialize_parameters() {
+            let parameters = SetStateParameters::new(
+                &HomeId::new("home"),
+                &[ModuleState::new(&ModuleId::new("70:ee:50:00:00:01")).monitoring(Monitoring::Off)],
+            );
+
+            let json = serde_json::to_string(&parameters).unwrap();
+
+            assert_eq!(
+                json,
+                r#"{"home":{"id":"home","modules":[{"id":"70:ee:50:00:00:01","monitoring":"off"}]}}"#
+            );
+        }
+
+        #[test]
+        fn serialize_floodlight() {
+            let parameters = SetStateParameters::new(
+                &HomeId::new("home"),
+                &[ModuleState::new(&ModuleId::new("70:ee:50:00:00:02")).floodlight(FloodlightMode::Auto)],
+            );
+
+            let json = serde_json::to_string(&parameters).unwrap();
+
+            assert_eq!(
+                json,
+                r#"{"home":{"id":"home","modules":[{"id":"70:ee:50:00:00:02","floodlight":"auto"}]}}"#
+            );
+        }
+
+        #[test]
+        fn monitoring_and_floodlight_round_trip() {
+            assert_round_trip!(Monitoring::On, Monitoring::Off);
+
+            assert_round_trip!(FloodlightMode::Auto, FloodlightMode::On, FloodlightMode::Off);
+        }
+
+        #[test]
+        fn serialize_rooms() {
+            let parameters =
+                SetStateParameters::new(&HomeId::new("home"), &[]).rooms(&[RoomState::new(&RoomId::new("2255"))
+                    .mode(Mode::Manual)
+                    .temperature(19.5)
+                    .end_time(1689865621)]);
+
+            let json = serde_json::to_string(&parameters).unwrap();
+
+            assert_eq!(
+                json,
+                r#"{"home":{"id":"home","rooms":[{"id":"2255","therm_setpoint_mode":"manual","therm_setpoint_temperature":19.5,"therm_setpoint_end_time":1689865621}]}}"#
+            );
+        }
+
+        #[test]
+        fn serialize_switch() {
+            let parameters = switch_parameters(
+                &HomeId::new("home"),
+                &ModuleId::new("12:34:56:00:00:01"),
+                &ModuleId::new("12:34:56:00:00:00"),
+                true,
+            );
+
+            let json = serde_json::to_string(&parameters).unwrap();
+
+            assert_eq!(
+                json,
+                r#"{"home":{"id":"home","modules":[{"id":"12:34:56:00:00:01","bridge":"12:34:56:00:00:00","on":true}]}}"#
+            );
+        }
+
+        #[test]
+        fn serialize_shutter() {
+            let home_id = HomeId::new("home");
+            let module_id = ModuleId::new("12:34:56:00:00:02");
+            let bridge = ModuleId::new("12:34:56:00:00:00");
+
+            for (position, expected) in [
+                (ShutterPosition::Open, 100),
+                (ShutterPosition::Close, 0),
+                (ShutterPosition::Stop, -1),
+                (ShutterPosition::Percent(40), 40),
+                (ShutterPosition::Percent(150), 100),
+            ] {
+                let json = serde_json::to_string(&shutter_parameters(&home_id, &module_id, &bridge, position)).unwrap();
+
+                assert_eq!(
+                    json,
+                    format!(
+                        r#"{{"home":{{"id":"home","modules":[{{"id":"12:34:56:00:00:02","bridge":"12:34:56:00:00:00","target_position":{}}}]}}}}"#,
+                        expected
+                    )
+                );
+            }
+        }
+
+        #[test]
+        fn serialize_brightness() {
+            let home_id = HomeId::new("home");
+            let module_id = ModuleId::new("12:34:56:00:00:03");
+            let bridge = ModuleId::new("12:34:56:00:00:00");
+
+            for (brightness, expected) in [(60, 60), (255, 100)] {
+                let json =
+                    serde_json::to_string(&brightness_parameters(&home_id, &module_id, &bridge, brightness)).unwrap();
+
+                assert_eq!(
+                    json,
+                    format!(
+                        r#"{{"home":{{"id":"home","modules":[{{"id":"12:34:56:00:00:03","bridge":"12:34:56:00:00:00","on":true,"brightness":{}}}]}}}}"#,
+                        expected
+                    )
+                );
+            }
+        }
+
+        #[test]
+        fn serialize_scenario() {
+            let parameters = scenario_parameters(&HomeId::new("home"), &ModuleId::new("70:ee:50:00:00:10"), "wake_up");
+
+            let json = serde_json::to_string(&parameters).unwrap();
+
+            assert_eq!(
+                json,
+                r#"{"home":{"id":"home","modules":[{"id":"70:ee:50:00:00:10","scenario":"wake_up"}]}}"#
+            );
+        }
+
+        #[test]
+        fn partial_failure() {
+            let json = r#"{
+                "status": "ok",
+                "time_server": 1689865621,
+                "body": {
+                  "errors": [
+                    {
+                      "code": 6,
+                      "id": "12:34:56:00:00:02"
+                    }
+                  ]
+                }
+              }"#;
+
+            let response: SetStateResponse = serde_json::from_str(json).unwrap();
+            let results =
+                response.module_results(&[&ModuleId::new("12:34:56:00:00:01"), &ModuleId::new("12:34:56:00:00:02")]);
+
+            assert_eq!(results["12:34:56:00:00:01"], Ok(()));
+            assert_eq!(results["12:34:56:00:00:02"].as_ref().unwrap_err().code, 6);
+        }
+    }
+}
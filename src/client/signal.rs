@@ -0,0 +1,85 @@
+use super::{get_homecoachs_data::HomeCoach, get_station_data, module_status::ModuleStatus};
+use serde::{Deserialize, Serialize};
+
+// Lower bounds of the average and bad bands; both signals are reported as positive values where lower is better, e.g.
+// rf_status from 60 (full) to 90 (low) and wifi_status from 56 (good) to 86 (bad)
+const RF_AVERAGE: i64 = 80;
+const RF_BAD: i64 = 90;
+const WIFI_AVERAGE: i64 = 71;
+const WIFI_BAD: i64 = 86;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SignalQuality {
+    Good,
+    Average,
+    Bad,
+}
+
+impl SignalQuality {
+    /// For the radio signal between a module and its station or gateway, `rf_status` or `rf_strength`.
+    pub fn from_rf(strength: i64) -> Self {
+        match strength {
+            x if x >= RF_BAD => SignalQuality::Bad,
+            x if x >= RF_AVERAGE => SignalQuality::Average,
+            _ => SignalQuality::Good,
+        }
+    }
+
+    /// For the wifi signal of a station, home coach, camera or gateway, `wifi_status` or `wifi_strength`.
+    pub fn from_wifi(strength: i64) -> Self {
+        match strength {
+            x if x >= WIFI_BAD => SignalQuality::Bad,
+            x if x >= WIFI_AVERAGE => SignalQuality::Average,
+            _ => SignalQuality::Good,
+        }
+    }
+}
+
+impl ModuleStatus {
+    pub fn rf_quality(&self) -> Option<SignalQuality> {
+        self.rf_strength().map(SignalQuality::from_rf)
+    }
+
+    pub fn wifi_quality(&self) -> Option<SignalQuality> {
+        self.wifi_strength().map(SignalQuality::from_wifi)
+    }
+}
+
+impl get_station_data::Device {
+    /// `None` for favorite stations of other users, which don't report their wifi signal.
+    pub fn wifi_quality(&self) -> Option<SignalQuality> {
+        (!self.favorite).then(|| SignalQuality::from_wifi(self.wifi_status as i64))
+    }
+}
+
+impl get_station_data::Module {
+    pub fn rf_quality(&self) -> SignalQuality {
+        SignalQuality::from_rf(self.rf_status as i64)
+    }
+}
+
+impl HomeCoach {
+    pub fn wifi_quality(&self) -> SignalQuality {
+        SignalQuality::from_wifi(self.wifi_status as i64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod signal_quality {
+        use super::*;
+
+        #[test]
+        fn documented_thresholds() {
+            assert_eq!(SignalQuality::from_rf(60), SignalQuality::Good);
+            assert_eq!(SignalQuality::from_rf(85), SignalQuality::Average);
+            assert_eq!(SignalQuality::from_rf(90), SignalQuality::Bad);
+            assert_eq!(SignalQuality::from_wifi(56), SignalQuality::Good);
+            assert_eq!(SignalQuality::from_wifi(71), SignalQuality::Average);
+            assert_eq!(SignalQuality::from_wifi(86), SignalQuality::Bad);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use super::{
+    get_events::{Event, SecurityEventType},
+    module_status::SmokeDetectorStatus,
+    timestamp::{epoch, Epoch},
+};
+use crate::errors::{NetatmoError, Result};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+// Netatmo offers no public call to hush a smoke or CO alarm; this can only be done on the device or in the app.
+
+/// Typed view on the events reported by smoke (NSD) and carbon monoxide (NCO) detectors.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum SmokeEvent {
+    Smoke { detected: bool },
+    Hush { active: bool },
+    Tampered { tampered: bool },
+    DetectionChamber(DetectionChamberStatus),
+    Battery { very_low: bool },
+    SoundTest { failed: bool },
+    WifiStatus { connected: bool },
+    CarbonMonoxide(CarbonMonoxideLevel),
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DetectionChamberStatus {
+    #[default]
+    Clean,
+    Dusty,
+}
+
+impl fmt::Display for DetectionChamberStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            DetectionChamberStatus::Clean => "clean",
+            DetectionChamberStatus::Dusty => "dusty",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for DetectionChamberStatus {
+    type Err = NetatmoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "clean" => Ok(DetectionChamberStatus::Clean),
+            "dusty" => Ok(DetectionChamberStatus::Dusty),
+            _ => Err(NetatmoError::FailedToReadResponse),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum CarbonMonoxideLevel {
+    #[default]
+    Ok,
+    PreAlarm,
+    Alarm,
+}
+
+impl Event {
+    /// Interprets the event as a smoke or CO detector event, using its `sub_type`.
+    pub fn smoke_event(&self) -> Option<SmokeEvent> {
+        let sub_type = self.sub_type?;
+        let event = match self.type_field {
+            SecurityEventType::Smoke => SmokeEvent::Smoke {
+                detected: sub_type == 1,
+            },
+            SecurityEventType::Hush => SmokeEvent::Hush { active: sub_type == 1 },
+            SecurityEventType::Tampered => SmokeEvent::Tampered {
+                tampered: sub_type == 1,
+            },
+            SecurityEventType::DetectionChamberStatus if sub_type == 1 => {
+                SmokeEvent::DetectionChamber(DetectionChamberStatus::Dusty)
+            }
+            SecurityEventType::DetectionChamberStatus => SmokeEvent::DetectionChamber(DetectionChamberStatus::Clean),
+            SecurityEventType::BatteryStatus => SmokeEvent::Battery {
+                very_low: sub_type == 1,
+            },
+            SecurityEventType::SoundTest => SmokeEvent::SoundTest { failed: sub_type == 1 },
+            SecurityEventType::WifiStatus => SmokeEvent::WifiStatus {
+                connected: sub_type == 1,
+            },
+            SecurityEventType::CoDetected => SmokeEvent::CarbonMonoxide(match sub_type {
+                0 => CarbonMonoxideLevel::Ok,
+                1 => CarbonMonoxideLevel::PreAlarm,
+                _ => CarbonMonoxideLevel::Alarm,
+            }),
+            _ => return None,
+        };
+
+        Some(event)
+    }
+}
+
+/// The latest period smoke was detected in, `end` is missing while smoke is still detected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SmokeEpisode {
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub start: Epoch,
+    #[serde(default, with = "epoch::option")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
+    pub end: Option<Epoch>,
+}
+
+impl SmokeDetectorStatus {
+    pub fn last_smoke_episode(&self) -> Option<SmokeEpisode> {
+        let start = self.last_smoke_detected_start_time?;
+        let end = self.last_smoke_detected_end_time.filter(|end| *end >= start);
+
+        Some(SmokeEpisode { start, end })
+    }
+
+    pub fn detection_chamber(&self) -> Option<DetectionChamberStatus> {
+        self.detection_chamber_status
+            .as_ref()
+            .and_then(|x| DetectionChamberStatus::from_str(x).ok())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::test_util::{assert_round_trip, at, events, TIME};
+
+    mod smoke_event {
+        use super::*;
+
+        #[test]
+        fn from_event() {
+            let event = Event {
+                sub_type: Some(1),
+                ..events::event("event", SecurityEventType::Smoke, TIME)
+            };
+
+            assert_eq!(event.smoke_event(), Some(SmokeEvent::Smoke { detected: true }));
+        }
+
+        #[test]
+        fn ongoing_smoke_episode() {
+            let module: SmokeDetectorStatus = serde_json::from_str(
+                r#"{"id": "70:ee:50:00:00:01", "type": "NSD", "firmware_revision": 1, "last_smoke_detected_start_time": 1689865621, "last_smoke_detected_end_time": 1689860000}"#,
+            )
+            .unwrap();
+
+            assert_eq!(
+                module.last_smoke_episode(),
+                Some(SmokeEpisode {
+                    start: at(1689865621),
+                    end: None
+                })
+            );
+        }
+
+        #[test]
+        fn detection_chamber_round_trip() {
+            assert_round_trip!(DetectionChamberStatus::Clean, DetectionChamberStatus::Dusty);
+        }
+    }
+}
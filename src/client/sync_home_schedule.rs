@@ -0,0 +1,234 @@
+use super::{
+    get_homes_data::{Schedule, Timetable, Zone},
+    ids::{HomeId, ScheduleId},
+    timestamp::{epoch, Epoch},
+};
+use crate::{client::NetatmoClient, errors::Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SyncHomeScheduleParameters {
+    home_id: HomeId,
+    schedule_id: ScheduleId,
+    timetable: Vec<Timetable>,
+    zones: Vec<Zone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hg_temp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    away_temp: Option<i64>,
+}
+
+impl SyncHomeScheduleParameters {
+    pub fn new(home_id: &HomeId, schedule_id: &ScheduleId, timetable: &[Timetable], zones: &[Zone]) -> Self {
+        SyncHomeScheduleParameters {
+            home_id: home_id.clone(),
+            schedule_id: schedule_id.clone(),
+            timetable: timetable.to_vec(),
+            zones: zones.to_vec(),
+            name: None,
+            hg_temp: None,
+            away_temp: None,
+        }
+    }
+
+    pub fn home_id(&self) -> &HomeId {
+        &self.home_id
+    }
+
+    pub fn schedule_id(&self) -> &ScheduleId {
+        &self.schedule_id
+    }
+
+    pub fn timetable(&self) -> &[Timetable] {
+        &self.timetable
+    }
+
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones
+    }
+
+    pub fn name(self, name: &str) -> Self {
+        SyncHomeScheduleParameters {
+            name: Some(name.to_string()),
+            ..self
+        }
+    }
+
+    pub fn hg_temp(self, hg_temp: i64) -> Self {
+        SyncHomeScheduleParameters {
+            hg_temp: Some(hg_temp),
+            ..self
+        }
+    }
+
+    pub fn away_temp(self, away_temp: i64) -> Self {
+        SyncHomeScheduleParameters {
+            away_temp: Some(away_temp),
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleSync {
+    InSync,
+    OutOfSync(SyncHomeScheduleParameters),
+}
+
+/// Compares a locally defined schedule with the one returned by `homesdata`.
+///
+/// Timetable and zones are always part of the payload as the API requires them, the remaining fields are only sent
+/// if they differ. The remote schedule's id is used, so the local one doesn't need to know it.
+pub fn diff_schedule(home_id: &HomeId, local: &Schedule, remote: &Schedule) -> ScheduleSync {
+    let local_timetable = normalized_timetable(local);
+    let local_zones = normalized_zones(local);
+
+    let timetable_changed = local_timetable != normalized_timetable(remote);
+    let zones_changed = local_zones != normalized_zones(remote);
+    let name_changed = local.name != remote.name;
+    let hg_temp_changed = local.hg_temp != remote.hg_temp;
+    let away_temp_changed = local.away_temp != remote.away_temp;
+
+    if !(timetable_changed || zones_changed || name_changed || hg_temp_changed || away_temp_changed) {
+        return ScheduleSync::InSync;
+    }
+
+    let mut parameters = SyncHomeScheduleParameters::new(home_id, &remote.id, &local_timetable, &local_zones);
+    if name_changed {
+        parameters = parameters.name(&local.name);
+    }
+    if hg_temp_changed {
+        parameters = parameters.hg_temp(local.hg_temp);
+    }
+    if away_temp_changed {
+        parameters = parameters.away_temp(local.away_temp);
+    }
+
+    ScheduleSync::OutOfSync(parameters)
+}
+
+fn normalized_timetable(schedule: &Schedule) -> Vec<Timetable> {
+    let mut timetable = schedule.timetable.clone().unwrap_or_default();
+    timetable.sort_by_key(|x| x.m_offset);
+    timetable
+}
+
+fn normalized_zones(schedule: &Schedule) -> Vec<Zone> {
+    let mut zones = schedule.zones.clone().unwrap_or_default();
+    zones.sort_by_key(|x| x.id);
+    for zone in zones.iter_mut() {
+        if let Some(rooms) = zone.rooms.as_mut() {
+            rooms.sort_by(|a, b| a.id.cmp(&b.id));
+        }
+    }
+    zones
+}
+
+#[derive(Deserialize, Debug)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SyncHomeScheduleResponse {
+    pub status: String,
+    #[serde(with = "epoch")]
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
+    pub time_server: Epoch,
+}
+
+// cf. https://dev.netatmo.com/apidocumentation/energy#synchomeschedule
+pub async fn sync_home_schedule(
+    client: &NetatmoClient,
+    parameters: &SyncHomeScheduleParameters,
+) -> Result<SyncHomeScheduleResponse> {
+    client
+        .call_json(
+            "sync_home_schedule",
+            "https://api.netatmo.com/api/synchomeschedule",
+            parameters,
+        )
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{
+        get_homes_data::{RoomTemp, ZoneType},
+        ids::RoomId,
+    };
+
+    mod diff_schedule {
+        use super::*;
+
+        fn schedule() -> Schedule {
+            Schedule {
+                id: ScheduleId::new("remote"),
+                timetable: Some(vec![
+                    Timetable {
+                        zone_id: 0,
+                        m_offset: 0,
+                    },
+                    Timetable {
+                        zone_id: 1,
+                        m_offset: 480,
+                    },
+                ]),
+                zones: Some(vec![Zone {
+                    name: "Comfort".to_string(),
+                    id: 0,
+                    type_field: ZoneType::Day,
+                    rooms: Some(vec![
+                        RoomTemp {
+                            id: RoomId::new("1"),
+                            therm_setpoint_temperature: 20.0,
+                        },
+                        RoomTemp {
+                            id: RoomId::new("2"),
+                            therm_setpoint_temperature: 19.0,
+                        },
+                    ]),
+                }]),
+                name: "Default".to_string(),
+                default: false,
+                away_temp: 12,
+                hg_temp: 7,
+                type_field: "therm".to_string(),
+            }
+        }
+
+        #[test]
+        fn in_sync_ignoring_order() {
+            let remote = schedule();
+            let mut local = schedule();
+            local.id = ScheduleId::new("");
+            local.timetable.as_mut().unwrap().reverse();
+            local.zones.as_mut().unwrap()[0].rooms.as_mut().unwrap().reverse();
+
+            assert_eq!(
+                diff_schedule(&HomeId::new("home"), &local, &remote),
+                ScheduleSync::InSync
+            );
+        }
+
+        #[test]
+        fn out_of_sync_only_sends_changed_fields() {
+            let remote = schedule();
+            let mut local = schedule();
+            local.hg_temp = 8;
+
+            let expected = SyncHomeScheduleParameters::new(
+                &HomeId::new("home"),
+                &ScheduleId::new("remote"),
+                remote.timetable.as_ref().unwrap(),
+                remote.zones.as_ref().unwrap(),
+            )
+            .hg_temp(8);
+
+            assert_eq!(
+                diff_schedule(&HomeId::new("home"), &local, &remote),
+                ScheduleSync::OutOfSync(expected)
+            );
+        }
+    }
+}
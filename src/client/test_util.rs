@@ -0,0 +1,170 @@
+// Builders for response models in tests, taking the fields the API always sends as arguments and leaving all others
+// empty, as deserialization does for a minimal response.
+
+use crate::client::timestamp::{epoch, Epoch};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+/// Timestamp used for required times the tests don't look at, 2023-07-20T15:07:01Z.
+pub(crate) const TIME: i64 = 1689865621;
+
+/// The time `seconds` after the epoch, as models hold it.
+pub(crate) fn at(seconds: i64) -> Epoch {
+    epoch::from_seconds::<serde_json::Error>(seconds).unwrap()
+}
+
+/// Asserts that each value serializes as the string it displays as, and deserializes and parses back into itself.
+macro_rules! assert_round_trip {
+    ($($value:expr),+ $(,)?) => {
+        $(
+            let value = $value;
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(json, format!(r#""{}""#, value));
+            assert_eq!(serde_json::from_str(&json).ok(), Some(value.clone()));
+            assert_eq!(value.to_string().parse().ok(), Some(value));
+        )+
+    };
+}
+pub(crate) use assert_round_trip;
+
+fn build<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).expect("Test model is missing a required field")
+}
+
+pub(crate) mod events {
+    use super::*;
+    use crate::client::get_events::{Event, SecurityEventType, SubEvent};
+
+    pub(crate) fn event(id: &str, type_field: SecurityEventType, time: i64) -> Event {
+        build(json!({"id": id, "type": type_field, "time": time, "module_id": "70:ee:50:00:00:01"}))
+    }
+
+    pub(crate) fn sub_event(id: &str, type_field: SecurityEventType, time: i64) -> SubEvent {
+        build(json!({"id": id, "type": type_field, "time": time}))
+    }
+}
+
+pub(crate) mod homes_data {
+    use super::*;
+    use crate::client::{
+        get_homes_data::{Home, Module, Room, User},
+        module_type::ModuleType,
+        room_type::RoomType,
+    };
+
+    pub(crate) fn home(id: &str, name: &str, timezone: &str) -> Home {
+        build(json!({"id": id, "name": name, "timezone": timezone}))
+    }
+
+    pub(crate) fn room(id: &str, name: &str, type_field: RoomType) -> Room {
+        build(json!({"id": id, "name": name, "type": type_field}))
+    }
+
+    pub(crate) fn module(id: &str, type_field: ModuleType, name: &str) -> Module {
+        build(json!({"id": id, "type": type_field, "name": name, "setup_date": TIME}))
+    }
+
+    /// A user with metric units and the default preferences.
+    pub(crate) fn user(id: &str, email: &str) -> User {
+        build(json!({
+            "id": id,
+            "email": email,
+            "language": "en-US",
+            "locale": "en-US",
+            "feel_like_algorithm": 0,
+            "unit_pressure": 0,
+            "unit_system": 0,
+            "unit_wind": 0
+        }))
+    }
+}
+
+pub(crate) mod module_status {
+    use super::*;
+    use crate::client::module_status::CameraStatus;
+
+    pub(crate) fn camera(id: &str, vpn_url: Option<&str>, is_local: bool) -> CameraStatus {
+        build(json!({"id": id, "type": "NACamera", "firmware_revision": 301, "vpn_url": vpn_url, "is_local": is_local}))
+    }
+}
+
+pub(crate) mod public_data {
+    use super::*;
+    use crate::client::{
+        geo::Coordinates,
+        get_public_data::{PublicData, PublicStation},
+    };
+
+    pub(crate) fn station(id: &str, location: Coordinates) -> PublicStation {
+        build(json!({"_id": id, "place": {"location": location}}))
+    }
+
+    pub(crate) fn data(stations: Vec<PublicStation>) -> PublicData {
+        PublicData {
+            status: "ok".to_string(),
+            time_server: at(TIME),
+            body: stations,
+        }
+    }
+}
+
+pub(crate) mod server {
+    use std::sync::{Arc, Mutex};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// An HTTP server on localhost answering every request with `respond(url, path)`, given its own base url, for urls
+    /// the client doesn't hard code, e.g. those of cameras.
+    pub(crate) struct Server {
+        pub(crate) url: String,
+        paths: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Server {
+        pub(crate) async fn start<F>(respond: F) -> Self
+        where
+            F: Fn(&str, &str) -> (u16, String) + Send + 'static,
+        {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let paths = Arc::new(Mutex::new(vec![]));
+
+            let requested = paths.clone();
+            let base_url = url.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    let mut request = vec![];
+                    let mut buf = [0; 1024];
+                    while !request.windows(4).any(|x| x == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await.unwrap() {
+                            0 => break,
+                            n => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+
+                    let request = String::from_utf8_lossy(&request);
+                    let path = request.split_whitespace().nth(1).unwrap_or_default().to_string();
+                    let (status, body) = respond(&base_url, &path);
+                    requested.lock().unwrap().push(path);
+                    let response = format!(
+                        "HTTP/1.1 {} Mock\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                }
+            });
+
+            Server { url, paths }
+        }
+
+        /// The paths requested so far, in order.
+        pub(crate) fn paths(&self) -> Vec<String> {
+            self.paths.lock().unwrap().clone()
+        }
+    }
+}
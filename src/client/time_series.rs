@@ -0,0 +1,257 @@
+use super::get_measure::Type;
+use crate::errors::{NetatmoError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "csv")]
+mod csv;
+#[cfg(feature = "parquet")]
+mod parquet;
+#[cfg(feature = "polars")]
+mod polars;
+pub mod summary;
+
+/// Measurements ordered by time, with each value labeled by the type it was requested as.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TimeSeries {
+    types: Vec<Type>,
+    values: BTreeMap<i64, Vec<Option<f64>>>,
+}
+
+/// A hole between two consecutive points that are further apart than the expected step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Gap {
+    pub start: i64,
+    pub end: i64,
+}
+
+/// How to fill the points missing in a gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fill {
+    /// Adds points without values
+    Empty,
+    /// Interpolates each type linearly between the points around the gap, if both have a value for it
+    Linear,
+}
+
+/// The values of all types at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<'a> {
+    pub timestamp: i64,
+    types: &'a [Type],
+    values: &'a [Option<f64>],
+}
+
+impl<'a> Point<'a> {
+    pub(crate) fn new(timestamp: i64, types: &'a [Type], values: &'a [Option<f64>]) -> Self {
+        Point {
+            timestamp,
+            types,
+            values,
+        }
+    }
+
+    pub fn get(&self, measurement_type: &Type) -> Option<f64> {
+        let index = self.types.iter().position(|x| x == measurement_type)?;
+        self.values.get(index).copied().flatten()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = (&'a Type, Option<f64>)> {
+        self.types.iter().zip(self.values.iter().copied())
+    }
+}
+
+impl TimeSeries {
+    /// Creates a series from values in the order of `types`, i.e. the order they were requested in.
+    pub fn new<I>(types: &[Type], values: I) -> Self
+    where
+        I: IntoIterator<Item = (i64, Vec<Option<f64>>)>,
+    {
+        TimeSeries {
+            types: types.to_vec(),
+            values: values.into_iter().collect(),
+        }
+    }
+
+    pub fn types(&self) -> &[Type] {
+        &self.types
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn timestamps(&self) -> impl Iterator<Item = i64> + '_ {
+        self.values.keys().copied()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point<'_>> {
+        self.values
+            .iter()
+            .map(move |(timestamp, values)| Point::new(*timestamp, &self.types, values))
+    }
+
+    /// The values of one type, skipping points without a value for it.
+    pub fn get(&self, measurement_type: &Type) -> Vec<(i64, f64)> {
+        self.points()
+            .filter_map(|x| x.get(measurement_type).map(|v| (x.timestamp, v)))
+            .collect()
+    }
+
+    pub fn temperature(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::Temperature)
+    }
+
+    pub fn humidity(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::Humidity)
+    }
+
+    pub fn co2(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::CO2)
+    }
+
+    pub fn pressure(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::Pressure)
+    }
+
+    pub fn noise(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::Noise)
+    }
+
+    pub fn min_noise(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::MinNoise)
+    }
+
+    pub fn max_noise(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::MaxNoise)
+    }
+
+    pub fn rain(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::Rain)
+    }
+
+    pub fn wind_strength(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::WindStrength)
+    }
+
+    pub fn gust_strength(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::GustStrength)
+    }
+
+    pub fn energy(&self) -> Vec<(i64, f64)> {
+        self.get(&Type::SumEnergyElec)
+    }
+
+    /// Finds the gaps between points more than half a `step` later than expected, e.g. `Scale::seconds()`.
+    ///
+    /// Fails if `step` isn't positive.
+    pub fn gaps(&self, step: i64) -> Result<Vec<Gap>> {
+        if step <= 0 {
+            return Err(NetatmoError::invalid_parameter("step", "must be positive"));
+        }
+
+        let timestamps: Vec<i64> = self.timestamps().collect();
+        Ok(timestamps
+            .windows(2)
+            .filter(|x| x[1] - x[0] > step + step / 2)
+            .map(|x| Gap { start: x[0], end: x[1] })
+            .collect())
+    }
+
+    /// Adds points every `step` seconds within each gap, failing if `step` isn't positive.
+    pub fn fill_gaps(&self, step: i64, fill: Fill) -> Result<TimeSeries> {
+        let mut filled = self.clone();
+        for gap in self.gaps(step)? {
+            let before = &self.values[&gap.start];
+            let after = &self.values[&gap.end];
+            for timestamp in (gap.start + step..gap.end).step_by(step as usize) {
+                let values = match fill {
+                    Fill::Empty => vec![None; self.types.len()],
+                    Fill::Linear => {
+                        let ratio = (timestamp - gap.start) as f64 / (gap.end - gap.start) as f64;
+                        before
+                            .iter()
+                            .zip(after)
+                            .map(|(a, b)| Some(a.as_ref()? + (b.as_ref()? - a.as_ref()?) * ratio))
+                            .collect()
+                    }
+                };
+                filled.values.insert(timestamp, values);
+            }
+        }
+
+        Ok(filled)
+    }
+
+    /// Adds the points of `other`, which must have been requested with the same types.
+    pub fn extend(&mut self, other: TimeSeries) {
+        self.values.extend(other.values);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod time_series {
+        use super::*;
+
+        #[test]
+        fn ordered_typed_values() {
+            let series = TimeSeries::new(
+                &[Type::Temperature, Type::Humidity],
+                vec![(200, vec![Some(21.0), None]), (100, vec![Some(20.5), Some(45.0)])],
+            );
+
+            assert_eq!(series.timestamps().collect::<Vec<_>>(), vec![100, 200]);
+            assert_eq!(series.temperature(), vec![(100, 20.5), (200, 21.0)]);
+            assert_eq!(series.humidity(), vec![(100, 45.0)]);
+            assert_eq!(series.points().last().unwrap().get(&Type::Humidity), None);
+        }
+
+        #[test]
+        fn fill_gaps() {
+            let series = TimeSeries::new(
+                &[Type::Temperature, Type::Humidity],
+                vec![
+                    (0, vec![Some(20.0), Some(40.0)]),
+                    (300, vec![Some(21.0), None]),
+                    (1200, vec![Some(24.0), Some(50.0)]),
+                ],
+            );
+
+            assert_eq!(series.gaps(300), Ok(vec![Gap { start: 300, end: 1200 }]));
+
+            let filled = series.fill_gaps(300, Fill::Linear).unwrap();
+            assert_eq!(filled.timestamps().collect::<Vec<_>>(), vec![0, 300, 600, 900, 1200]);
+            assert_eq!(filled.temperature()[2], (600, 22.0));
+            assert_eq!(filled.humidity().len(), 2);
+        }
+
+        #[test]
+        fn reject_non_positive_step() {
+            let series = TimeSeries::new(
+                &[Type::Temperature],
+                vec![(0, vec![Some(20.0)]), (1200, vec![Some(24.0)])],
+            );
+
+            for step in [0, -300] {
+                assert!(matches!(
+                    series.gaps(step),
+                    Err(NetatmoError::InvalidParameter { name, .. }) if name == "step"
+                ));
+                assert!(matches!(
+                    series.fill_gaps(step, Fill::Empty),
+                    Err(NetatmoError::InvalidParameter { name, .. }) if name == "step"
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use super::TimeSeries;
+use crate::errors::{NetatmoError, Result};
+use std::io;
+
+impl TimeSeries {
+    /// Writes a header of `timestamp` and the requested types, then one row per point; missing values are left empty.
+    pub fn to_csv<W: io::Write>(&self, writer: &mut csv::Writer<W>) -> Result<()> {
+        let header = std::iter::once("timestamp".to_string()).chain(self.types.iter().map(|x| x.to_string()));
+        writer
+            .write_record(header)
+            .map_err(|_| NetatmoError::FailedToWriteExport)?;
+
+        for point in self.points() {
+            let row = std::iter::once(point.timestamp.to_string()).chain(
+                point
+                    .values()
+                    .map(|(_, v)| v.map(|v| v.to_string()).unwrap_or_default()),
+            );
+            writer
+                .write_record(row)
+                .map_err(|_| NetatmoError::FailedToWriteExport)?;
+        }
+
+        writer.flush().map_err(|_| NetatmoError::FailedToWriteExport)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::get_measure::Type;
+
+    mod to_csv {
+        use super::*;
+
+        #[test]
+        fn writes_header_and_rows() {
+            let series = TimeSeries::new(
+                &[Type::Temperature, Type::Humidity],
+                vec![(100, vec![Some(20.5), Some(45.0)]), (200, vec![Some(21.0), None])],
+            );
+            let mut writer = csv::Writer::from_writer(vec![]);
+
+            series.to_csv(&mut writer).unwrap();
+
+            let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+            assert_eq!(csv, "timestamp,Temperature,Humidity\n100,20.5,45\n200,21,\n");
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use super::TimeSeries;
+use crate::errors::{NetatmoError, Result};
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, TimestampSecondArray};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use std::{io, sync::Arc};
+
+impl TimeSeries {
+    /// Converts the series into a record batch with a UTC `timestamp` column and one nullable column per type.
+    pub fn to_record_batch(&self) -> Result<RecordBatch> {
+        let mut fields = vec![Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Second, Some("UTC".into())),
+            false,
+        )];
+        fields.extend(
+            self.types
+                .iter()
+                .map(|x| Field::new(x.to_string(), DataType::Float64, true)),
+        );
+
+        let timestamps = TimestampSecondArray::from_iter_values(self.timestamps()).with_timezone("UTC");
+        let mut columns: Vec<ArrayRef> = vec![Arc::new(timestamps)];
+        columns.extend((0..self.types.len()).map(|i| {
+            let values = self.values.values().map(|x| x.get(i).copied().flatten());
+            Arc::new(Float64Array::from_iter(values)) as ArrayRef
+        }));
+
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).map_err(|_| NetatmoError::FailedToWriteExport)
+    }
+
+    /// Writes the series as a single row group Parquet file.
+    pub fn write_parquet<W: io::Write + Send>(&self, writer: W) -> Result<()> {
+        let batch = self.to_record_batch()?;
+        let mut writer =
+            ArrowWriter::try_new(writer, batch.schema(), None).map_err(|_| NetatmoError::FailedToWriteExport)?;
+        writer.write(&batch).map_err(|_| NetatmoError::FailedToWriteExport)?;
+        writer
+            .close()
+            .map(|_| ())
+            .map_err(|_| NetatmoError::FailedToWriteExport)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::get_measure::Type;
+
+    mod to_record_batch {
+        use super::*;
+
+        #[test]
+        fn one_column_per_type() {
+            let series = TimeSeries::new(
+                &[Type::Temperature, Type::Humidity],
+                vec![(100, vec![Some(20.5), Some(45.0)]), (200, vec![Some(21.0), None])],
+            );
+
+            let batch = series.to_record_batch().unwrap();
+
+            assert_eq!(batch.num_rows(), 2);
+            assert_eq!(batch.num_columns(), 3);
+            assert_eq!(batch.column(2).null_count(), 1);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use super::TimeSeries;
+use crate::errors::{NetatmoError, Result};
+use polars::prelude::{Column, DataFrame, DataType, TimeUnit, TimeZone};
+
+impl TimeSeries {
+    /// Converts the series into a data frame with a UTC `timestamp` column and one nullable column per type.
+    pub fn into_dataframe(self) -> Result<DataFrame> {
+        let timestamps: Vec<i64> = self.timestamps().map(|x| x * 1000).collect();
+        let timestamps = Column::new("timestamp".into(), timestamps)
+            .cast(&DataType::Datetime(TimeUnit::Milliseconds, Some(TimeZone::UTC)))
+            .map_err(|_| NetatmoError::FailedToWriteExport)?;
+
+        let mut columns = vec![timestamps];
+        columns.extend(self.types.iter().enumerate().map(|(i, x)| {
+            let values: Vec<Option<f64>> = self.values.values().map(|v| v.get(i).copied().flatten()).collect();
+            Column::new(x.to_string().into(), values)
+        }));
+
+        DataFrame::new(columns).map_err(|_| NetatmoError::FailedToWriteExport)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::get_measure::Type;
+
+    mod into_dataframe {
+        use super::*;
+
+        #[test]
+        fn one_column_per_type() {
+            let series = TimeSeries::new(
+                &[Type::Temperature, Type::Humidity],
+                vec![(100, vec![Some(20.5), Some(45.0)]), (200, vec![Some(21.0), None])],
+            );
+
+            let df = series.into_dataframe().unwrap();
+
+            assert_eq!(df.shape(), (2, 3));
+            assert_eq!(df.column("Humidity").unwrap().null_count(), 1);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use super::TimeSeries;
+use crate::client::get_measure::Type;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum Bucket {
+    Hour,
+    Day,
+}
+
+impl Bucket {
+    fn seconds(&self) -> i64 {
+        match self {
+            Bucket::Hour => 60 * 60,
+            Bucket::Day => 24 * 60 * 60,
+        }
+    }
+}
+
+/// Statistics over the values of one local hour or day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Summary {
+    /// UTC timestamp of the start of the local hour or day
+    pub start: i64,
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+impl TimeSeries {
+    /// Summarizes the values of a type per local hour or day.
+    ///
+    /// `utc_offset` returns the offset from UTC in seconds of the home's timezone at a UTC timestamp, so buckets follow
+    /// the local wall clock; days in which DST starts or ends have 23 or 25 hours. Values for which it returns `None`
+    /// are skipped.
+    pub fn summarize<F>(&self, measurement_type: &Type, bucket: Bucket, utc_offset: F) -> Vec<Summary>
+    where
+        F: Fn(i64) -> Option<i64>,
+    {
+        let mut summaries: Vec<Summary> = vec![];
+        for (timestamp, value) in self.get(measurement_type) {
+            let Some(offset) = utc_offset(timestamp) else {
+                continue;
+            };
+            let local = timestamp + offset;
+            let local_start = local - local.rem_euclid(bucket.seconds());
+            // The offset at the start of the bucket may differ from the one at the value if DST changed since. Buckets
+            // are told apart by their UTC start, as the local hour repeated when DST ends starts twice.
+            let start = local_start - utc_offset(local_start - offset).unwrap_or(offset);
+
+            match summaries.last_mut() {
+                Some(summary) if summary.start == start => {
+                    summary.min = summary.min.min(value);
+                    summary.max = summary.max.max(value);
+                    summary.mean += (value - summary.mean) / (summary.count + 1) as f64;
+                    summary.count += 1;
+                }
+                _ => summaries.push(Summary {
+                    start,
+                    count: 1,
+                    min: value,
+                    max: value,
+                    mean: value,
+                }),
+            }
+        }
+
+        summaries
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod summarize {
+        use super::*;
+
+        // Central European Time, DST ending on 2023-10-29 at 01:00 UTC
+        fn cet(timestamp: i64) -> Option<i64> {
+            if timestamp < 1698541200 {
+                Some(2 * 3600)
+            } else {
+                Some(3600)
+            }
+        }
+
+        #[test]
+        fn daily_across_dst_end() {
+            // 2023-10-28 22:00 UTC is midnight local time, the day then lasts 25 hours
+            let day_start = 1698530400;
+            let series = TimeSeries::new(
+                &[Type::Temperature],
+                vec![
+                    (day_start - 60, vec![Some(30.0)]),
+                    (day_start, vec![Some(10.0)]),
+                    (day_start + 24 * 3600 + 1800, vec![Some(14.0)]),
+                    (day_start + 25 * 3600, vec![Some(30.0)]),
+                ],
+            );
+
+            let summaries = series.summarize(&Type::Temperature, Bucket::Day, cet);
+
+            assert_eq!(summaries.len(), 3);
+            assert_eq!(
+                summaries[1],
+                Summary {
+                    start: day_start,
+                    count: 2,
+                    min: 10.0,
+                    max: 14.0,
+                    mean: 12.0,
+                }
+            );
+            assert_eq!(summaries[2].start, day_start + 25 * 3600);
+        }
+
+        #[test]
+        fn hourly_across_dst_end() {
+            // 2023-10-29 00:00 and 01:00 UTC both start at 02:00 local time
+            let first_hour = 1698537600;
+            let series = TimeSeries::new(
+                &[Type::Temperature],
+                vec![
+                    (first_hour + 600, vec![Some(10.0)]),
+                    (first_hour + 1800, vec![Some(12.0)]),
+                    (first_hour + 3600 + 1800, vec![Some(20.0)]),
+                ],
+            );
+
+            let summaries = series.summarize(&Type::Temperature, Bucket::Hour, cet);
+
+            assert_eq!(summaries.len(), 2);
+            assert_eq!(
+                summaries[0],
+                Summary {
+                    start: first_hour,
+                    count: 2,
+                    min: 10.0,
+                    max: 12.0,
+                    mean: 11.0,
+                }
+            );
+            assert_eq!(summaries[1].start, first_hour + 3600);
+            assert_eq!(summaries[1].count, 1);
+        }
+    }
+}
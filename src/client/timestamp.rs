@@ -0,0 +1,107 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A point in time reported by the APIs, a `DateTime<Utc>` with the `chrono` feature and seconds since the epoch
+/// otherwise; either way it serializes as seconds since the epoch, like the APIs send it.
+#[cfg(feature = "chrono")]
+pub type Epoch = chrono::DateTime<chrono::Utc>;
+#[cfg(not(feature = "chrono"))]
+pub type Epoch = i64;
+
+/// An IANA timezone name reported for homes and stations, e.g. `Europe/Paris`, a `chrono_tz::Tz` with the `chrono-tz`
+/// feature and a `String` otherwise.
+#[cfg(feature = "chrono-tz")]
+pub type TimeZone = chrono_tz::Tz;
+#[cfg(not(feature = "chrono-tz"))]
+pub type TimeZone = String;
+
+/// A point in time that parameters convert into seconds since the epoch, as all APIs expect them.
+pub trait Timestamp {
+    fn epoch_seconds(&self) -> i64;
+}
+
+impl Timestamp for i64 {
+    fn epoch_seconds(&self) -> i64 {
+        *self
+    }
+}
+
+impl Timestamp for SystemTime {
+    fn epoch_seconds(&self) -> i64 {
+        match self.duration_since(UNIX_EPOCH) {
+            Ok(x) => x.as_secs() as i64,
+            Err(x) => -(x.duration().as_secs() as i64),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> Timestamp for chrono::DateTime<Tz> {
+    fn epoch_seconds(&self) -> i64 {
+        self.timestamp()
+    }
+}
+
+/// (De)serializes an [`Epoch`] from seconds since the epoch, for `#[serde(with = "epoch")]`.
+pub(crate) mod epoch {
+    use super::{Epoch, Timestamp};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn from_seconds<E: Error>(seconds: i64) -> Result<Epoch, E> {
+        #[cfg(feature = "chrono")]
+        return chrono::DateTime::from_timestamp(seconds, 0)
+            .ok_or_else(|| E::custom(format!("timestamp {} is out of range", seconds)));
+        #[cfg(not(feature = "chrono"))]
+        return Ok(seconds);
+    }
+
+    pub(crate) fn serialize<S: Serializer>(time: &Epoch, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(time.epoch_seconds())
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Epoch, D::Error> {
+        from_seconds(i64::deserialize(deserializer)?)
+    }
+
+    /// For optional fields, which also need `#[serde(default)]`.
+    pub(crate) mod option {
+        use super::*;
+
+        pub(crate) fn serialize<S: Serializer>(time: &Option<Epoch>, serializer: S) -> Result<S::Ok, S::Error> {
+            match time {
+                Some(time) => serializer.serialize_some(&time.epoch_seconds()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Epoch>, D::Error> {
+            Option::<i64>::deserialize(deserializer)?.map(from_seconds).transpose()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    mod timestamp {
+        use super::*;
+
+        #[test]
+        fn epoch_seconds() {
+            assert_eq!(1689865621i64.epoch_seconds(), 1689865621);
+            assert_eq!(
+                (UNIX_EPOCH + Duration::from_secs(1689865621)).epoch_seconds(),
+                1689865621
+            );
+            assert_eq!((UNIX_EPOCH - Duration::from_secs(60)).epoch_seconds(), -60);
+        }
+
+        #[cfg(feature = "chrono")]
+        #[test]
+        fn chrono_epoch_seconds() {
+            let datetime = chrono::DateTime::from_timestamp(1689865621, 0).unwrap();
+            assert_eq!(datetime.epoch_seconds(), 1689865621);
+        }
+    }
+}
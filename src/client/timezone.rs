@@ -0,0 +1,77 @@
+use super::{
+    datetime::from_epoch,
+    time_series::{Point, TimeSeries},
+};
+use crate::errors::{NetatmoError, Result};
+use chrono::{DateTime, Offset};
+use chrono_tz::Tz;
+
+/// Parses an IANA timezone name, e.g. `Europe/Paris`, as the APIs report them for homes and stations.
+pub fn parse_timezone(name: &str) -> Result<Tz> {
+    name.parse().map_err(|_| NetatmoError::InvalidTimezone {
+        value: name.to_string(),
+    })
+}
+
+/// Converts seconds since the epoch into the local date and time of `tz`.
+pub fn from_epoch_in(seconds: i64, tz: &Tz) -> Option<DateTime<Tz>> {
+    from_epoch(seconds).map(|x| x.with_timezone(tz))
+}
+
+/// The offset from UTC in seconds of `tz` at a UTC timestamp, e.g. for [`TimeSeries::summarize`]; `None` if the
+/// timestamp is out of range.
+pub fn utc_offset(tz: &Tz) -> impl Fn(i64) -> Option<i64> + '_ {
+    move |timestamp| from_epoch_in(timestamp, tz).map(|x| i64::from(x.offset().fix().local_minus_utc()))
+}
+
+impl Point<'_> {
+    pub fn local_datetime(&self, tz: &Tz) -> Option<DateTime<Tz>> {
+        from_epoch_in(self.timestamp, tz)
+    }
+}
+
+impl TimeSeries {
+    pub fn local_datetimes<'a>(&'a self, tz: &'a Tz) -> impl Iterator<Item = DateTime<Tz>> + 'a {
+        self.timestamps().filter_map(|x| from_epoch_in(x, tz))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::{get_measure::Type, test_util::homes_data, time_series::summary::Bucket};
+
+    mod timezone {
+        use super::*;
+
+        #[test]
+        fn local_time() {
+            let home = homes_data::home("home", "Home", "Europe/Paris");
+            let tz = home.timezone;
+
+            assert_eq!(
+                from_epoch_in(1689865621, &tz).unwrap().to_rfc3339(),
+                "2023-07-20T17:07:01+02:00"
+            );
+            assert_eq!(utc_offset(&tz)(1700000000), Some(3600));
+            assert_eq!(utc_offset(&tz)(i64::MAX), None);
+            assert!(matches!(
+                parse_timezone("EDT"),
+                Err(NetatmoError::InvalidTimezone { value }) if value == "EDT"
+            ));
+
+            // 2023-07-20 00:30 and 23:30 in Paris fall on the same local day
+            let series = TimeSeries::new(
+                &[Type::Temperature],
+                vec![(1689805800, vec![Some(20.0)]), (1689888600, vec![Some(22.0)])],
+            );
+            let summaries = series.summarize(&Type::Temperature, Bucket::Day, utc_offset(&tz));
+            assert_eq!(summaries.len(), 1);
+            assert_eq!(summaries[0].start, 1689804000);
+            assert_eq!(
+                series.local_datetimes(&tz).next().unwrap().to_rfc3339(),
+                "2023-07-20T00:30:00+02:00"
+            );
+        }
+    }
+}
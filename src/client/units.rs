@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The unit a user chose to display wind speeds in, cf. `unit_wind`. Unknown preferences are kept as `Other` and
+/// shown in km/h.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "i64", into = "i64")]
+#[non_exhaustive]
+pub enum WindUnit {
+    #[default]
+    Kph,
+    Mph,
+    MetersPerSecond,
+    Beaufort,
+    Knots,
+    Other(i64),
+}
+
+impl WindUnit {
+    pub fn from_preference(preference: i64) -> Option<Self> {
+        match preference {
+            0 => Some(WindUnit::Kph),
+            1 => Some(WindUnit::Mph),
+            2 => Some(WindUnit::MetersPerSecond),
+            3 => Some(WindUnit::Beaufort),
+            4 => Some(WindUnit::Knots),
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for WindUnit {
+    fn from(preference: i64) -> Self {
+        WindUnit::from_preference(preference).unwrap_or(WindUnit::Other(preference))
+    }
+}
+
+impl From<WindUnit> for i64 {
+    fn from(unit: WindUnit) -> Self {
+        match unit {
+            WindUnit::Kph => 0,
+            WindUnit::Mph => 1,
+            WindUnit::MetersPerSecond => 2,
+            WindUnit::Beaufort => 3,
+            WindUnit::Knots => 4,
+            WindUnit::Other(preference) => preference,
+        }
+    }
+}
+
+impl fmt::Display for WindUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            WindUnit::Kph | WindUnit::Other(_) => "km/h",
+            WindUnit::Mph => "mph",
+            WindUnit::MetersPerSecond => "m/s",
+            WindUnit::Beaufort => "Bft",
+            WindUnit::Knots => "kn",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+// Upper bounds in km/h of Beaufort forces 0 to 11
+const BEAUFORT_LIMITS: [f64; 12] = [1.0, 6.0, 12.0, 20.0, 29.0, 39.0, 50.0, 62.0, 75.0, 89.0, 103.0, 118.0];
+
+/// A wind speed, as reported by the API in km/h.
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct WindSpeed(f64);
+
+impl WindSpeed {
+    pub fn from_kph(kph: f64) -> Self {
+        WindSpeed(kph)
+    }
+
+    pub fn kph(&self) -> f64 {
+        self.0
+    }
+
+    pub fn meters_per_second(&self) -> f64 {
+        self.0 / 3.6
+    }
+
+    pub fn mph(&self) -> f64 {
+        self.0 / 1.609344
+    }
+
+    pub fn knots(&self) -> f64 {
+        self.0 / 1.852
+    }
+
+    pub fn beaufort(&self) -> u8 {
+        BEAUFORT_LIMITS.iter().take_while(|x| self.0 >= **x).count() as u8
+    }
+
+    pub fn in_unit(&self, unit: WindUnit) -> f64 {
+        match unit {
+            WindUnit::Kph | WindUnit::Other(_) => self.kph(),
+            WindUnit::Mph => self.mph(),
+            WindUnit::MetersPerSecond => self.meters_per_second(),
+            WindUnit::Beaufort => f64::from(self.beaufort()),
+            WindUnit::Knots => self.knots(),
+        }
+    }
+}
+
+/// The unit a user chose to display pressures in, cf. `unit_pressure`. Unknown preferences are kept as `Other` and
+/// shown in mbar.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(from = "i64", into = "i64")]
+#[non_exhaustive]
+pub enum PressureUnit {
+    #[default]
+    Mbar,
+    InHg,
+    MmHg,
+    Other(i64),
+}
+
+impl PressureUnit {
+    pub fn from_preference(preference: i64) -> Option<Self> {
+        match preference {
+            0 => Some(PressureUnit::Mbar),
+            1 => Some(PressureUnit::InHg),
+            2 => Some(PressureUnit::MmHg),
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for PressureUnit {
+    fn from(preference: i64) -> Self {
+        PressureUnit::from_preference(preference).unwrap_or(PressureUnit::Other(preference))
+    }
+}
+
+impl From<PressureUnit> for i64 {
+    fn from(unit: PressureUnit) -> Self {
+        match unit {
+            PressureUnit::Mbar => 0,
+            PressureUnit::InHg => 1,
+            PressureUnit::MmHg => 2,
+            PressureUnit::Other(preference) => preference,
+        }
+    }
+}
+
+impl fmt::Display for PressureUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            PressureUnit::Mbar | PressureUnit::Other(_) => "mbar",
+            PressureUnit::InHg => "inHg",
+            PressureUnit::MmHg => "mmHg",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// An atmospheric pressure, as reported by the API in mbar, which equals hPa.
+#[derive(Default, Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct Pressure(f64);
+
+impl Pressure {
+    pub fn from_mbar(mbar: f64) -> Self {
+        Pressure(mbar)
+    }
+
+    pub fn mbar(&self) -> f64 {
+        self.0
+    }
+
+    pub fn hpa(&self) -> f64 {
+        self.0
+    }
+
+    pub fn in_hg(&self) -> f64 {
+        self.0 / 33.863_886
+    }
+
+    pub fn mm_hg(&self) -> f64 {
+        self.0 / 1.333_224
+    }
+
+    pub fn in_unit(&self, unit: PressureUnit) -> f64 {
+        match unit {
+            PressureUnit::Mbar | PressureUnit::Other(_) => self.mbar(),
+            PressureUnit::InHg => self.in_hg(),
+            PressureUnit::MmHg => self.mm_hg(),
+        }
+    }
+
+    /// Formats the pressure with the precision the Netatmo app uses for the unit, e.g. `29.92 inHg`.
+    pub fn format(&self, unit: PressureUnit) -> String {
+        match unit {
+            PressureUnit::Mbar | PressureUnit::Other(_) => format!("{:.1} {}", self.mbar(), unit),
+            PressureUnit::InHg => format!("{:.2} {}", self.in_hg(), unit),
+            PressureUnit::MmHg => format!("{:.0} {}", self.mm_hg(), unit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod wind_speed {
+        use super::*;
+
+        #[test]
+        fn conversions() {
+            let speed = WindSpeed::from_kph(36.0);
+
+            assert_eq!(speed.in_unit(WindUnit::MetersPerSecond), 10.0);
+            assert_eq!(speed.in_unit(WindUnit::Beaufort), 5.0);
+            assert!((speed.knots() - 19.438).abs() < 0.001);
+            assert_eq!(WindSpeed::from_kph(0.5).beaufort(), 0);
+            assert_eq!(WindSpeed::from_kph(150.0).beaufort(), 12);
+        }
+    }
+
+    mod pressure {
+        use super::*;
+
+        #[test]
+        fn format() {
+            let pressure = Pressure::from_mbar(1013.25);
+
+            assert_eq!(pressure.format(PressureUnit::Mbar), "1013.2 mbar");
+            assert_eq!(pressure.format(PressureUnit::InHg), "29.92 inHg");
+            assert_eq!(pressure.format(PressureUnit::MmHg), "760 mmHg");
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use crate::client::ids::ModuleId;
 use thiserror::Error;
 
 /// The error kind for errors that get returned in the crate
 #[derive(Eq, PartialEq, Debug, Error, Clone)]
+#[non_exhaustive]
 pub enum NetatmoError {
     #[error("Failed to deserialize JSON")]
     JsonDeserializationFailed,
@@ -20,6 +22,46 @@ pub enum NetatmoError {
 
     #[error("API call '{name}' failed for unknown reason with status code {status_code}")]
     UnknownApiCallFailure { name: String, status_code: u16 },
+
+    #[error("Media is not available anymore, its key has expired")]
+    MediaExpired,
+
+    #[error("Failed to write export")]
+    FailedToWriteExport,
+
+    #[error("Invalid parameter '{name}': {msg}")]
+    InvalidParameter { name: String, msg: String },
+
+    #[error("'{value}' is not a valid MAC address")]
+    InvalidMacAddress { value: String },
+
+    #[error("'{value}' is not a valid IANA timezone")]
+    InvalidTimezone { value: String },
+
+    #[error("Module {module_id} did not report the requested state")]
+    StateNotConfirmed { module_id: ModuleId },
+
+    #[error("Module {module_id} reported an error: {reason}")]
+    ModuleReportedError { module_id: ModuleId, reason: String },
+}
+
+impl NetatmoError {
+    pub(crate) fn invalid_parameter(name: &str, msg: &str) -> Self {
+        NetatmoError::InvalidParameter {
+            name: name.to_string(),
+            msg: msg.to_string(),
+        }
+    }
+
+    /// Whether the call was rejected because the application or user exceeded Netatmo's rate limits
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            // cf. https://dev.netatmo.com/apidocumentation/general#status-ok
+            NetatmoError::ApiCallFailed { code, .. } => *code == 26,
+            NetatmoError::UnknownApiCallFailure { status_code, .. } => *status_code == 429,
+            _ => false,
+        }
+    }
 }
 
 pub type Result<T> = ::std::result::Result<T, NetatmoError>;
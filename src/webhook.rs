@@ -0,0 +1,267 @@
+use crate::{
+    client::ids::{HomeId, ModuleId, PersonId},
+    errors::{NetatmoError, Result},
+};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod broadcast;
+
+/// The payloads Netatmo POSTs to a webhook registered via `add_webhook`.
+///
+/// Payloads of unknown event types, or known types whose shape doesn't match the model, are kept as raw JSON in
+/// `Unknown` instead of failing.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum WebhookPayload {
+    PersonSeen(PersonSeen),
+    Movement(Movement),
+    SmokeDetected(SmokeDetected),
+    DoorbellRing(DoorbellRing),
+    DoorbellAccepted(DoorbellRing),
+    DoorbellMissed(DoorbellRing),
+    Connection(ConnectionChange),
+    Disconnection(ConnectionChange),
+    ButtonPress(RemoteButtonPress),
+    Unknown(Value),
+}
+
+impl WebhookPayload {
+    pub fn from_slice(body: &[u8]) -> Result<Self> {
+        serde_json::from_slice(body).map_err(|_| NetatmoError::JsonDeserializationFailed)
+    }
+
+    pub fn home_id(&self) -> Option<&str> {
+        match self {
+            WebhookPayload::PersonSeen(x) => Some(x.home_id.as_str()),
+            WebhookPayload::Movement(x) => Some(x.home_id.as_str()),
+            WebhookPayload::SmokeDetected(x) => Some(x.home_id.as_str()),
+            WebhookPayload::DoorbellRing(x)
+            | WebhookPayload::DoorbellAccepted(x)
+            | WebhookPayload::DoorbellMissed(x) => Some(x.home_id.as_str()),
+            WebhookPayload::Connection(x) | WebhookPayload::Disconnection(x) => Some(x.home_id.as_str()),
+            WebhookPayload::ButtonPress(x) => Some(x.home_id.as_str()),
+            WebhookPayload::Unknown(x) => x.get("home_id").and_then(Value::as_str),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WebhookPayload {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let event_type = value.get("event_type").and_then(Value::as_str).unwrap_or_default();
+
+        let payload = match event_type {
+            "person" => typed(&value).map(WebhookPayload::PersonSeen),
+            "movement" => typed(&value).map(WebhookPayload::Movement),
+            "smoke" => typed(&value).map(WebhookPayload::SmokeDetected),
+            "incoming_call" => typed(&value).map(WebhookPayload::DoorbellRing),
+            "accepted_call" => typed(&value).map(WebhookPayload::DoorbellAccepted),
+            "missed_call" => typed(&value).map(WebhookPayload::DoorbellMissed),
+            "connection" => typed(&value).map(WebhookPayload::Connection),
+            "disconnection" => typed(&value).map(WebhookPayload::Disconnection),
+            "button_press" => typed(&value).map(WebhookPayload::ButtonPress),
+            _ => None,
+        };
+
+        Ok(payload.unwrap_or(WebhookPayload::Unknown(value)))
+    }
+}
+
+fn typed<T: DeserializeOwned>(value: &Value) -> Option<T> {
+    T::deserialize(value).ok()
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct PersonSeen {
+    pub event_type: String,
+    pub push_type: String,
+    pub event_id: String,
+    pub home_id: HomeId,
+    pub home_name: Option<String>,
+    pub camera_id: ModuleId,
+    pub device_id: Option<ModuleId>,
+    #[serde(default)]
+    pub persons: Vec<WebhookPerson>,
+    pub snapshot_id: Option<String>,
+    pub snapshot_key: Option<String>,
+    pub snapshot_url: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct WebhookPerson {
+    pub id: PersonId,
+    pub face_id: Option<String>,
+    pub face_key: Option<String>,
+    pub face_url: Option<String>,
+    pub is_known: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct Movement {
+    pub event_type: String,
+    pub push_type: String,
+    pub event_id: String,
+    pub home_id: HomeId,
+    pub home_name: Option<String>,
+    pub camera_id: ModuleId,
+    pub device_id: Option<ModuleId>,
+    pub snapshot_id: Option<String>,
+    pub snapshot_key: Option<String>,
+    pub snapshot_url: Option<String>,
+    pub vignette_id: Option<String>,
+    pub vignette_key: Option<String>,
+    pub vignette_url: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct SmokeDetected {
+    pub event_type: String,
+    pub push_type: String,
+    pub event_id: String,
+    pub home_id: HomeId,
+    pub home_name: Option<String>,
+    pub device_id: ModuleId,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct DoorbellRing {
+    pub event_type: String,
+    pub push_type: String,
+    pub event_id: String,
+    pub home_id: HomeId,
+    pub home_name: Option<String>,
+    pub device_id: ModuleId,
+    pub session_id: Option<String>,
+    pub snapshot_url: Option<String>,
+    pub vignette_url: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct ConnectionChange {
+    pub event_type: String,
+    pub push_type: String,
+    pub home_id: HomeId,
+    pub home_name: Option<String>,
+    pub camera_id: Option<ModuleId>,
+    pub device_id: Option<ModuleId>,
+    pub message: Option<String>,
+}
+
+/// A press on a wireless remote switch (NLT), with the undocumented `sub_type` as reported, cf. `Event::button_press`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub struct RemoteButtonPress {
+    pub event_type: String,
+    pub push_type: String,
+    pub event_id: Option<String>,
+    pub home_id: HomeId,
+    pub home_name: Option<String>,
+    pub device_id: Option<ModuleId>,
+    pub module_id: ModuleId,
+    pub sub_type: Option<i64>,
+    pub message: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod webhook_payload {
+        use super::*;
+
+        #[test]
+        fn parse_person_seen() {
+            let json = r#"{
+                "user_id": "...",
+                "user": {
+                  "id": "...",
+                  "email": "john@doe.com"
+                },
+                "event_id": "...",
+                "event_type": "person",
+                "camera_id": "70:ee:50:00:00:01",
+                "device_id": "70:ee:50:00:00:01",
+                "home_id": "...",
+                "home_name": "Home",
+                "persons": [
+                  {
+                    "id": "...",
+                    "face_id": "...",
+                    "face_key": "...",
+                    "is_known": true,
+                    "face_url": "https://netatmocameraimage.blob.core.windows.net/production/..."
+                  }
+                ],
+                "snapshot_id": "...",
+                "snapshot_key": "...",
+                "snapshot_url": "https://netatmocameraimage.blob.core.windows.net/production/...",
+                "message": "John seen",
+                "push_type": "NACamera-person"
+              }"#;
+
+            let payload = WebhookPayload::from_slice(json.as_bytes());
+
+            assert!(matches!(payload, Ok(WebhookPayload::PersonSeen(_))));
+        }
+
+        #[test]
+        fn parse_button_press() {
+            let json = r#"{
+                "user_id": "...",
+                "event_id": "...",
+                "event_type": "button_press",
+                "device_id": "70:ee:50:00:00:04",
+                "module_id": "00:04:74:00:00:01",
+                "home_id": "...",
+                "home_name": "Home",
+                "sub_type": 3,
+                "push_type": "NLT-button_press"
+              }"#;
+
+            let payload = WebhookPayload::from_slice(json.as_bytes()).unwrap();
+
+            let WebhookPayload::ButtonPress(press) = payload else {
+                panic!("Expected a button press, got {:?}", payload);
+            };
+            assert_eq!(press.module_id, "00:04:74:00:00:01");
+            assert_eq!(press.sub_type, Some(3));
+        }
+
+        #[test]
+        fn parse_unknown_event_type() {
+            let json = r#"{
+                "user_id": "...",
+                "event_type": "webhook_activation",
+                "push_type": "webhook_activation"
+              }"#;
+
+            let payload = WebhookPayload::from_slice(json.as_bytes());
+
+            assert!(matches!(payload, Ok(WebhookPayload::Unknown(_))));
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use super::{broadcast::WebhookBroadcaster, WebhookPayload};
+use axum::{body::Bytes, extract::State, http::StatusCode, routing::post, Router};
+use log::{debug, warn};
+use std::future::Future;
+
+/// Returns a router accepting Netatmo's webhook POSTs on `/` and handing each parsed payload to `callback`.
+///
+/// Netatmo disables webhooks which don't answer successfully, so the callback should return quickly.
+pub fn router<F, Fut>(callback: F) -> Router
+where
+    F: Fn(WebhookPayload) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    Router::new().route("/", post(handle::<F, Fut>)).with_state(callback)
+}
+
+/// Returns a router publishing every parsed payload to the broadcaster's subscribers.
+pub fn broadcast_router(broadcaster: WebhookBroadcaster) -> Router {
+    router(move |payload| {
+        broadcaster.publish(payload);
+        futures::future::ready(())
+    })
+}
+
+async fn handle<F, Fut>(State(callback): State<F>, body: Bytes) -> StatusCode
+where
+    F: Fn(WebhookPayload) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    match WebhookPayload::from_slice(&body) {
+        Ok(payload) => {
+            debug!("Received webhook payload: {:?}", payload);
+            callback(payload).await;
+            StatusCode::OK
+        }
+        Err(e) => {
+            warn!("Failed to parse webhook payload: {}", e);
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use tokio::sync::mpsc;
+    use tower::ServiceExt;
+
+    mod router {
+        use super::*;
+
+        const BUTTON_PRESS: &str = r#"{
+            "event_type": "button_press",
+            "push_type": "NLT-button_press",
+            "home_id": "...",
+            "module_id": "00:04:74:00:00:01",
+            "sub_type": 1
+          }"#;
+
+        async fn post(router: Router, body: &str) -> StatusCode {
+            let request = Request::post("/").body(Body::from(body.to_string())).unwrap();
+            router.oneshot(request).await.unwrap().status()
+        }
+
+        #[tokio::test]
+        async fn dispatches_payload() {
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+            let router = router(move |payload| {
+                sender.send(payload).unwrap();
+                futures::future::ready(())
+            });
+
+            assert_eq!(post(router, BUTTON_PRESS).await, StatusCode::OK);
+            assert!(matches!(receiver.recv().await, Some(WebhookPayload::ButtonPress(_))));
+        }
+
+        #[tokio::test]
+        async fn rejects_invalid_body() {
+            let router = router(|_| async { panic!("Callback called for an invalid body") });
+
+            assert_eq!(post(router, "not json").await, StatusCode::BAD_REQUEST);
+        }
+
+        #[tokio::test]
+        async fn broadcasts_payload() {
+            let broadcaster = WebhookBroadcaster::new(8);
+            let mut receiver = broadcaster.subscribe();
+
+            assert_eq!(post(broadcast_router(broadcaster), BUTTON_PRESS).await, StatusCode::OK);
+            assert!(matches!(receiver.recv().await, Ok(WebhookPayload::ButtonPress(_))));
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use super::WebhookPayload;
+use crate::errors::Result;
+use futures::stream::{self, Stream};
+use log::warn;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Fans parsed webhook payloads out to any number of subscribers within the process.
+#[derive(Debug, Clone)]
+pub struct WebhookBroadcaster {
+    sender: broadcast::Sender<WebhookPayload>,
+}
+
+impl WebhookBroadcaster {
+    /// Creates a broadcaster keeping at most `capacity` payloads for subscribers which lag behind; a `capacity` of 0
+    /// keeps a single payload.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        WebhookBroadcaster { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<WebhookPayload> {
+        self.sender.subscribe()
+    }
+
+    /// Subscribes as a stream; payloads a slow subscriber missed are skipped with a warning.
+    pub fn stream(&self) -> impl Stream<Item = WebhookPayload> {
+        stream::unfold(self.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(payload) => return Some((payload, receiver)),
+                    Err(RecvError::Lagged(skipped)) => warn!("Webhook subscriber lagged, skipped {} payloads", skipped),
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// Publishes a payload and returns the number of subscribers it reached.
+    pub fn publish(&self, payload: WebhookPayload) -> usize {
+        self.sender.send(payload).unwrap_or(0)
+    }
+
+    /// Parses the body of a webhook request and publishes it.
+    pub fn publish_body(&self, body: &[u8]) -> Result<usize> {
+        WebhookPayload::from_slice(body).map(|x| self.publish(x))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::StreamExt;
+
+    mod webhook_broadcaster {
+        use super::*;
+
+        #[tokio::test]
+        async fn reaches_all_subscribers() {
+            let broadcaster = WebhookBroadcaster::new(8);
+            let mut first = broadcaster.subscribe();
+            let second = broadcaster.stream();
+            futures::pin_mut!(second);
+
+            let reached = broadcaster
+                .publish_body(br#"{"event_type": "webhook_activation", "push_type": "webhook_activation"}"#)
+                .unwrap();
+
+            assert_eq!(reached, 2);
+            assert!(matches!(first.recv().await, Ok(WebhookPayload::Unknown(_))));
+            assert!(matches!(second.next().await, Some(WebhookPayload::Unknown(_))));
+        }
+
+        #[tokio::test]
+        async fn zero_capacity_keeps_one_payload() {
+            let broadcaster = WebhookBroadcaster::new(0);
+            let mut receiver = broadcaster.subscribe();
+
+            let reached = broadcaster
+                .publish_body(br#"{"event_type": "webhook_activation", "push_type": "webhook_activation"}"#)
+                .unwrap();
+
+            assert_eq!(reached, 1);
+            assert!(matches!(receiver.recv().await, Ok(WebhookPayload::Unknown(_))));
+        }
+    }
+}
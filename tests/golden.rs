@@ -0,0 +1,415 @@
+//! Parses the recorded, redacted responses in `tests/fixtures` and serializes them again, so renamed fields and wrong
+//! serde attributes fail here instead of when the API answers.
+//!
+//! Add a fixture for every new endpoint or webhook event, and list the fields the models don't keep on purpose.
+
+use netatmo_rs::{
+    client::{
+        add_webhook::AddWebhookResponse,
+        drop_webhook::DropWebhookResponse,
+        get_events::{Events, SecurityEventType, VideoStatus},
+        get_home_status::{GatewayLinkState, HomeStatus, HomeStatusErrorCode, ModuleBatteryState, ThermSetpointMode},
+        get_homecoachs_data::{HealthIndex, HomeCoachData},
+        get_homes_data::{HomesData, ThermMode, ZoneType},
+        get_last_event_of::LastEventOf,
+        get_measure::Measure,
+        get_next_events::NextEvents,
+        get_public_data::PublicData,
+        get_scenarios::Scenarios,
+        get_station_data::{StationData, WeatherDashboard},
+        module_status::ModuleStatus,
+        module_type::ModuleType,
+        room_type::RoomType,
+        set_persons_away::SetPersonsAwayResponse,
+        set_persons_home::SetPersonsHomeResponse,
+        set_room_thermpoint::SetRoomThermpointResponse,
+        set_state::SetStateResponse,
+        sync_home_schedule::SyncHomeScheduleResponse,
+        timestamp::Timestamp,
+    },
+    webhook::WebhookPayload,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{fs, path::Path};
+
+fn fixture(name: &str) -> Value {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+    let json = fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("{} is not valid JSON: {}", name, e))
+}
+
+fn parse<T: DeserializeOwned>(name: &str) -> T {
+    serde_json::from_value(fixture(name)).unwrap_or_else(|e| panic!("Failed to deserialize {}: {}", name, e))
+}
+
+/// Parses a fixture and checks that serializing the model keeps every field of the fixture but the `unmodeled` ones,
+/// given as JSON pointers with `*` for any array index or key, and that the serialized model parses to the same model.
+fn round_trip<T: DeserializeOwned + Serialize>(name: &str, unmodeled: &[&str]) -> T {
+    let model: T = parse(name);
+    let serialized = serde_json::to_value(&model).unwrap();
+
+    let mut dropped = vec![];
+    dropped_fields(&fixture(name), &serialized, "", &mut dropped);
+    dropped.retain(|path| !unmodeled.iter().any(|pattern| matches_pointer(pattern, path)));
+    assert!(
+        dropped.is_empty(),
+        "{} loses fields when serialized: {:?}",
+        name,
+        dropped
+    );
+
+    let again: T = serde_json::from_value(serialized.clone())
+        .unwrap_or_else(|e| panic!("Failed to deserialize {} after serializing it: {}", name, e));
+    assert_eq!(
+        serde_json::to_value(&again).unwrap(),
+        serialized,
+        "{} changes when parsed again",
+        name
+    );
+
+    model
+}
+
+/// Collects the paths of the values in `original` that are missing or `null` in `serialized`.
+fn dropped_fields(original: &Value, serialized: &Value, path: &str, dropped: &mut Vec<String>) {
+    let children: Vec<(String, &Value, Option<&Value>)> = match original {
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v, serialized.get(k))).collect(),
+        Value::Array(values) => values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i.to_string(), v, serialized.get(i)))
+            .collect(),
+        _ => return,
+    };
+
+    for (key, value, serialized_value) in children {
+        let child_path = format!("{}/{}", path, key);
+        match serialized_value {
+            None | Some(Value::Null) if !value.is_null() => dropped.push(child_path),
+            Some(serialized_value) => dropped_fields(value, serialized_value, &child_path, dropped),
+            None => {}
+        }
+    }
+}
+
+fn matches_pointer(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+
+    pattern.len() == path.len() && pattern.iter().zip(path).all(|(a, b)| *a == "*" || *a == b)
+}
+
+mod client {
+    use super::*;
+
+    #[test]
+    fn get_homes_data() {
+        let homes_data: HomesData = round_trip("client/get_homes_data.json", &["/body/homes/*/schedules/*/selected"]);
+
+        let home = &homes_data.body.homes.as_ref().unwrap()[0];
+        assert_eq!(home.id, "5c8a3b1e9f2d4e6a7b0c1d2e");
+        assert_eq!(home.timezone.to_string(), "Europe/Paris");
+        assert_eq!(home.coordinates.unwrap().latitude, 48.856614);
+        assert_eq!(home.therm_mode, Some(ThermMode::Schedule));
+        assert_eq!(home.extra.keys().collect::<Vec<_>>(), ["temperature_control_mode"]);
+
+        let rooms = home.rooms.as_ref().unwrap();
+        assert_eq!(rooms[0].type_field, RoomType::LivingRoom);
+        assert!(rooms.iter().all(|x| x.extra.is_empty()));
+
+        let modules = home.modules.as_ref().unwrap();
+        assert_eq!(modules[1].type_field, ModuleType::NATherm1);
+        assert_eq!(modules[1].bridge.as_ref().unwrap(), "70:ee:50:00:00:01");
+        assert!(modules.iter().all(|x| x.extra.is_empty()));
+        assert_eq!(home.heated_rooms().len(), 2);
+
+        let persons = home.persons.as_ref().unwrap();
+        assert!(persons[0].face_url.is_some());
+        assert!(!persons[1].is_known());
+
+        let schedule = &home.schedules.as_ref().unwrap()[0];
+        assert_eq!(schedule.timetable.as_ref().unwrap()[1].m_offset, 420);
+        assert_eq!(schedule.zones.as_ref().unwrap()[1].type_field, ZoneType::Night);
+        assert_eq!(homes_data.body.user.email, "user@example.com");
+    }
+
+    #[test]
+    fn get_home_status() {
+        let home_status: HomeStatus = round_trip("client/get_home_status.json", &[]);
+
+        let home = &home_status.body.home;
+        let modules = home.modules.as_ref().unwrap();
+        assert!(matches!(modules[0], ModuleStatus::Relay(_)));
+        assert!(matches!(
+            &modules[1],
+            ModuleStatus::Thermostat(x) if x.battery_state == Some(ModuleBatteryState::High) && x.boiler_status == Some(true)
+        ));
+        assert!(matches!(modules[2], ModuleStatus::Valve(_)));
+        assert!(matches!(&modules[4], ModuleStatus::Camera(x) if x.extra.keys().all(|k| k == "monitoring")));
+        assert!(matches!(&modules[5], ModuleStatus::SmokeDetector(x) if x.extra.is_empty()));
+        assert!(matches!(
+            &modules[6],
+            ModuleStatus::Gateway(x) if x.wifi_state == Some(GatewayLinkState::Connected) && x.is_gateway_connected() == Some(true)
+        ));
+        assert!(matches!(&modules[7], ModuleStatus::Switch(x) if x.power == Some(42)));
+
+        let rooms = home.rooms.as_ref().unwrap();
+        assert_eq!(rooms[0].therm_measured_temperature, Some(19.6));
+        assert_eq!(
+            rooms[0].therm_setpoint_start_time.map(|x| x.epoch_seconds()),
+            Some(1689850800)
+        );
+        assert_eq!(rooms[1].therm_setpoint_mode, Some(ThermSetpointMode::Manual));
+        assert_eq!(
+            rooms[1].therm_setpoint_start_time.map(|x| x.epoch_seconds()),
+            Some(1689860000)
+        );
+        assert_eq!(
+            home.persons.as_ref().unwrap()[0].last_seen.map(|x| x.epoch_seconds()),
+            Some(1689864000)
+        );
+
+        assert_eq!(home_status.errors()[0].code, HomeStatusErrorCode::Unreachable);
+        assert_eq!(
+            home_status.failed_modules()[0].module.unwrap().id(),
+            "09:00:00:00:00:02"
+        );
+    }
+
+    #[test]
+    fn get_events() {
+        let events: Events = round_trip("client/get_events.json", &[]);
+
+        let events = &events.body.home.events;
+        assert_eq!(events.len(), 4);
+        // Events that fail to parse are kept raw instead of failing the whole response
+        assert!(events.iter().all(|x| x.raw.is_none()));
+        assert!(events.iter().all(|x| x.extra.is_empty()));
+
+        assert_eq!(events[0].type_field, SecurityEventType::Person);
+        assert_eq!(events[0].is_arrival, Some(true));
+        assert_eq!(events[0].video_status, Some(VideoStatus::Available));
+        assert!(events[0].snapshot.as_ref().unwrap().url.is_some());
+
+        let subevents = events[1].subevents.as_ref().unwrap();
+        assert_eq!(subevents[0].type_field, SecurityEventType::Human);
+        assert_eq!(subevents[0].offset, Some(2));
+        assert!(subevents[0].vignette.is_some());
+
+        assert_eq!(events[2].sub_type, Some(0));
+        assert_eq!(events[3].video_status, Some(VideoStatus::Deleted));
+    }
+
+    #[test]
+    fn get_last_event_of() {
+        let last_event_of: LastEventOf = round_trip("client/get_last_event_of.json", &[]);
+
+        let events = &last_event_of.body.events_list;
+        assert!(events.iter().all(|x| x.raw.is_none()));
+        assert_eq!(
+            events[0].person_id.as_ref().unwrap(),
+            "91827364-5a4b-3c2d-1e0f-a9b8c7d6e5f4"
+        );
+        assert_eq!(events[0].snapshot.as_ref().unwrap().key.as_deref(), Some("redacted"));
+        assert_eq!(events[1].type_field, SecurityEventType::Movement);
+    }
+
+    #[test]
+    fn get_next_events() {
+        let next_events: NextEvents = round_trip("client/get_next_events.json", &[]);
+
+        let events = &next_events.body.events_list;
+        assert_eq!(events[0].type_field, SecurityEventType::DailySummary);
+        assert_eq!(events[1].type_field, SecurityEventType::Connection);
+        assert!(events[1].raw.is_none());
+        // Event types the crate doesn't know keep the event as sent
+        assert_eq!(events[2].type_field, SecurityEventType::Other("new_module".to_string()));
+        assert!(events[2].raw.is_some());
+    }
+
+    #[test]
+    fn get_scenarios() {
+        let scenarios: Scenarios = round_trip("client/get_scenarios.json", &[]);
+
+        let scenarios = &scenarios.body.home.scenarios;
+        assert_eq!(scenarios.len(), 3);
+        assert_eq!(scenarios[0].type_field, "wake_up");
+        assert!(!scenarios[1].editable);
+        assert_eq!(scenarios[2].name.as_deref(), Some("Movie night"));
+        assert!(scenarios[2].deletable);
+    }
+
+    #[test]
+    fn get_public_data() {
+        let public_data: PublicData = round_trip("client/get_public_data.json", &[]);
+
+        let station = &public_data.body[0];
+        assert_eq!(station.id, "70:ee:50:00:00:10");
+        assert_eq!(station.place.location.longitude, 2.3486);
+        assert_eq!(station.place.city.as_deref(), Some("Paris"));
+        assert_eq!(station.mark, Some(10));
+        assert!(station.extra.is_empty());
+
+        let measure = &station.measures["02:00:00:00:00:10"];
+        assert_eq!(
+            measure.res.as_ref().unwrap()["1689865500"],
+            vec![Some(23.4), Some(54.0)]
+        );
+        assert_eq!(measure.types.as_ref().unwrap(), &["temperature", "humidity"]);
+        assert_eq!(station.measures["05:00:00:00:00:10"].rain_24h, Some(1.515));
+        assert_eq!(station.measures["06:00:00:00:00:10"].gust_strength.unwrap().kph(), 22.0);
+        assert_eq!(
+            station.module_types.as_ref().unwrap()["06:00:00:00:00:10"],
+            ModuleType::NAModule2
+        );
+    }
+
+    #[test]
+    fn get_station_data() {
+        let station_data: StationData = round_trip("client/get_station_data.json", &[]);
+
+        let device = &station_data.body.devices[0];
+        assert_eq!(device.type_info, ModuleType::NAMain);
+        assert_eq!(device.place.timezone.to_string(), "Europe/Paris");
+        assert_eq!(device.dashboard_data.co2, Some(455));
+        assert_eq!(device.extra.keys().collect::<Vec<_>>(), ["cipher_id"]);
+        assert!(device.dashboard_data.extra.is_empty());
+        assert!(matches!(device.dashboard(), Some(WeatherDashboard::Main(_))));
+
+        assert_eq!(device.modules[0].battery_vp, 6190);
+        assert!(matches!(
+            device.modules[0].dashboard(),
+            Some(WeatherDashboard::Outdoor(_))
+        ));
+        assert_eq!(device.modules[1].dashboard_data.gust_angle, Some(240));
+        assert!(matches!(device.modules[1].dashboard(), Some(WeatherDashboard::Wind(_))));
+        assert!(device.modules.iter().all(|x| x.extra.is_empty()));
+
+        assert_eq!(station_data.body.owned_devices().count(), 1);
+        assert!(station_data.body.favorite_devices().all(|x| x.read_only));
+        assert_eq!(station_data.body.user.administrative.country.as_deref(), Some("FR"));
+    }
+
+    #[test]
+    fn get_homecoachs_data() {
+        let homecoachs_data: HomeCoachData = round_trip("client/get_homecoachs_data.json", &[]);
+
+        let homecoach = &homecoachs_data.body.devices[0];
+        assert_eq!(homecoach.type_info, ModuleType::NHC);
+        assert_eq!(homecoach.name.as_deref(), Some("Bedroom"));
+        assert_eq!(homecoach.extra.keys().collect::<Vec<_>>(), ["cipher_id"]);
+
+        let dashboard = homecoach.dashboard_data.as_ref().unwrap();
+        assert_eq!(dashboard.co2, Some(812));
+        assert_eq!(dashboard.health_idx, Some(HealthIndex::Fine));
+        assert!(dashboard.extra.is_empty());
+    }
+
+    #[test]
+    fn get_measure() {
+        let measure: Measure = round_trip("client/get_measure.json", &["/time_server"]);
+        let optimized: Measure = parse("client/get_measure_optimized.json");
+
+        assert_eq!(measure.status(), "ok");
+        let timestamps: Vec<i64> = measure.points().map(|x| x.timestamp).collect();
+        assert_eq!(timestamps, [1689859800, 1689861600, 1689863400, 1689865200]);
+        assert_eq!(measure.time_series().len(), 4);
+        assert_eq!(
+            optimized.points().map(|x| x.timestamp).collect::<Vec<_>>(),
+            [1689859800, 1689861600, 1689865200]
+        );
+    }
+
+    #[test]
+    fn command_responses() {
+        let set_state: SetStateResponse = parse("client/set_state.json");
+        assert_eq!(set_state.body.unwrap().errors[0].id, "05:00:00:00:00:02");
+
+        let set_room_thermpoint: SetRoomThermpointResponse = parse("client/set_room_thermpoint.json");
+        assert_eq!(set_room_thermpoint.time_server.epoch_seconds(), 1689865621);
+        let set_persons_away: SetPersonsAwayResponse = parse("client/set_persons_away.json");
+        assert_eq!(set_persons_away.status, "ok");
+        let set_persons_home: SetPersonsHomeResponse = parse("client/set_persons_home.json");
+        assert_eq!(set_persons_home.status, "ok");
+        let sync_home_schedule: SyncHomeScheduleResponse = parse("client/sync_home_schedule.json");
+        assert_eq!(sync_home_schedule.status, "ok");
+        let add_webhook: AddWebhookResponse = parse("client/add_webhook.json");
+        assert_eq!(add_webhook.status, "ok");
+        let drop_webhook: DropWebhookResponse = parse("client/drop_webhook.json");
+        assert_eq!(drop_webhook.status, "ok");
+    }
+}
+
+mod webhook {
+    use super::*;
+
+    // Every payload names the user it was sent for, which the models don't keep
+    const UNMODELED: &[&str] = &["/user_id", "/user"];
+
+    #[test]
+    fn person() {
+        let payload: WebhookPayload = round_trip("webhook/person.json", UNMODELED);
+
+        let WebhookPayload::PersonSeen(person_seen) = payload else {
+            panic!("Expected a person, got {:?}", payload);
+        };
+        assert_eq!(person_seen.camera_id, "70:ee:50:00:00:02");
+        assert!(person_seen.persons[0].is_known);
+        assert_eq!(person_seen.message.as_deref(), Some("Alex seen"));
+    }
+
+    #[test]
+    fn movement() {
+        let payload: WebhookPayload = round_trip("webhook/movement.json", UNMODELED);
+
+        let WebhookPayload::Movement(movement) = payload else {
+            panic!("Expected a movement, got {:?}", payload);
+        };
+        assert_eq!(movement.push_type, "NACamera-movement");
+        assert!(movement.vignette_url.is_some());
+    }
+
+    #[test]
+    fn smoke() {
+        let payload: WebhookPayload = round_trip("webhook/smoke.json", UNMODELED);
+
+        assert_eq!(payload.home_id(), Some("5c8a3b1e9f2d4e6a7b0c1d2e"));
+        let WebhookPayload::SmokeDetected(smoke) = payload else {
+            panic!("Expected a smoke detection, got {:?}", payload);
+        };
+        assert_eq!(smoke.device_id, "70:ee:50:00:00:03");
+    }
+
+    #[test]
+    fn incoming_call() {
+        let payload: WebhookPayload = round_trip("webhook/incoming_call.json", UNMODELED);
+
+        let WebhookPayload::DoorbellRing(ring) = payload else {
+            panic!("Expected a doorbell ring, got {:?}", payload);
+        };
+        assert_eq!(ring.device_id, "70:ee:50:00:00:08");
+        assert_eq!(ring.session_id.as_deref(), Some("8f9a0b1c-2d3e-4f5a-6b7c-8d9e0f1a2b3c"));
+    }
+
+    #[test]
+    fn disconnection() {
+        let payload: WebhookPayload = round_trip("webhook/disconnection.json", UNMODELED);
+
+        let WebhookPayload::Disconnection(disconnection) = payload else {
+            panic!("Expected a disconnection, got {:?}", payload);
+        };
+        assert_eq!(disconnection.camera_id.unwrap(), "70:ee:50:00:00:02");
+    }
+
+    #[test]
+    fn button_press() {
+        let payload: WebhookPayload = round_trip("webhook/button_press.json", UNMODELED);
+
+        let WebhookPayload::ButtonPress(press) = payload else {
+            panic!("Expected a button press, got {:?}", payload);
+        };
+        assert_eq!(press.module_id, "00:00:00:00:00:09");
+        assert_eq!(press.sub_type, Some(1));
+    }
+}